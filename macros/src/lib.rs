@@ -0,0 +1,167 @@
+//! Derive and attribute macros for [`bevy_sorting`](https://docs.rs/bevy_sorting).
+//!
+//! Use them through `bevy_sorting::prelude`, not directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, punctuated::Punctuated, Data, DeriveInput, Fields, Ident, Meta,
+    Token, Type,
+};
+
+/// Implements `AutoSetArg` for a struct deriving `SystemParam`. Auto-sets are inferred by applying
+/// `AutoSetArg` of each field in order.
+#[proc_macro_derive(AutoSetArg)]
+pub fn derive_auto_set_arg(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let trait_name = Ident::new("AutoSetArg", proc_macro2::Span::call_site());
+    match field_types(&input) {
+        Ok(fields) => impl_flow_trait(&input, &input.ident, &trait_name, &fields).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Implements `AutoSetArgInQuery` for a struct deriving `QueryData`. Auto-sets are inferred by
+/// applying `AutoSetArgInQuery` of each field in order. If the struct is marked with
+/// `#[query_data(mutable)]`, the generated `ReadOnly` variant gets an implementation as well.
+#[proc_macro_derive(AutoSetArgInQuery)]
+pub fn derive_auto_set_arg_in_query(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let trait_name = Ident::new("AutoSetArgInQuery", proc_macro2::Span::call_site());
+    let fields = match field_types(&input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let mutable = match is_mutable_query_data(&input) {
+        Ok(mutable) => mutable,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut output = impl_flow_trait(&input, &input.ident, &trait_name, &fields);
+    if mutable {
+        let read_only_ident = Ident::new(&format!("{}ReadOnly", input.ident), input.ident.span());
+        let read_only_fields = fields
+            .iter()
+            .map(|ty| parse_quote!(<#ty as ::bevy_sorting::__macro_exports::QueryData>::ReadOnly))
+            .collect::<Vec<Type>>();
+        output.extend(impl_flow_trait(
+            &input,
+            &read_only_ident,
+            &trait_name,
+            &read_only_fields,
+        ));
+    }
+    output.into()
+}
+
+/// Implements `AutoSetArgInQueryFilter` for a struct deriving `QueryFilter`. Auto-sets are
+/// inferred by applying `AutoSetArgInQueryFilter` of each field in order.
+#[proc_macro_derive(AutoSetArgInQueryFilter)]
+pub fn derive_auto_set_arg_in_query_filter(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let trait_name = Ident::new("AutoSetArgInQueryFilter", proc_macro2::Span::call_site());
+    match field_types(&input) {
+        Ok(fields) => impl_flow_trait(&input, &input.ident, &trait_name, &fields).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Implements `FlowPolicy` for the annotated type. Takes the default order of its writers and
+/// readers: `write_before_read`, `read_before_write` or `unordered`.
+///
+/// ```rust,ignore
+/// #[derive(Event)]
+/// #[flow(write_before_read)]
+/// struct LevelUpEvent;
+/// ```
+#[proc_macro_attribute]
+pub fn flow(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let order = parse_macro_input!(attr as Ident);
+    let input = parse_macro_input!(item as DeriveInput);
+    let variant = match order.to_string().as_str() {
+        "write_before_read" => quote!(WriteBeforeRead),
+        "read_before_write" => quote!(ReadBeforeWrite),
+        "unordered" => quote!(Unordered),
+        _ => {
+            return syn::Error::new_spanned(
+                order,
+                "expected `write_before_read`, `read_before_write` or `unordered`",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        #input
+
+        impl #impl_generics ::bevy_sorting::prelude::FlowPolicy for #ident #ty_generics #where_clause {
+            const ORDER: ::bevy_sorting::prelude::FlowOrder =
+                ::bevy_sorting::prelude::FlowOrder::#variant;
+        }
+    }
+    .into()
+}
+
+fn is_mutable_query_data(input: &DeriveInput) -> syn::Result<bool> {
+    for attr in &input.attrs {
+        if !attr.path().is_ident("query_data") {
+            continue;
+        }
+        let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        if nested.iter().any(|meta| meta.path().is_ident("mutable")) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn field_types(input: &DeriveInput) -> syn::Result<Vec<Type>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "auto-sets can only be derived for structs",
+        ));
+    };
+    let fields = match &data.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+    Ok(fields.into_iter().map(|field| field.ty.clone()).collect())
+}
+
+fn impl_flow_trait(
+    input: &DeriveInput,
+    self_ident: &Ident,
+    trait_name: &Ident,
+    fields: &[Type],
+) -> TokenStream2 {
+    let exports = quote!(::bevy_sorting::__macro_exports);
+    let trait_path = quote!(::bevy_sorting::prelude::#trait_name);
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for ty in fields {
+        where_clause.predicates.push(parse_quote!(#ty: #trait_path));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #trait_path for #self_ident #ty_generics #where_clause {
+            fn apply<S>(sys: #exports::ScheduleConfigs<S>) -> #exports::ScheduleConfigs<S>
+            where
+                S: #exports::Schedulable<
+                    Metadata = #exports::GraphInfo,
+                    GroupMetadata = #exports::Chain,
+                >,
+            {
+                #( let sys = <#fields as #trait_path>::apply(sys); )*
+                sys
+            }
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{LazyLock, Mutex},
+};
+
+use bevy::{
+    ecs::{
+        component::ComponentId,
+        query::{Access, ComponentAccessKind},
+        schedule::{
+            graph::{DiGraph, GraphInfo},
+            Chain, InternedScheduleLabel, NodeId, Schedulable, ScheduleBuildError,
+            ScheduleBuildPass, ScheduleConfigs, ScheduleGraph, ScheduleLabel,
+        },
+        system::ScheduleSystem,
+    },
+    prelude::{App, IntoScheduleConfigs, Plugin, SystemSet, World},
+};
+
+use crate::{
+    flow_graph::{event_resources, FlowGraph},
+    markers::{AnyFlowSet, FlowAccess, FlowSet, FlowTarget},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Implements the parts of [`ScheduleBuildPass`] that passes of this crate don't use. They act
+/// only in `build`, so dependencies are not tracked and no edges are added when sets collapse.
+macro_rules! build_only_pass {
+    () => {
+        type EdgeOptions = ();
+
+        fn add_dependency(
+            &mut self,
+            _from: bevy::ecs::schedule::NodeId,
+            _to: bevy::ecs::schedule::NodeId,
+            _options: Option<&Self::EdgeOptions>,
+        ) {
+        }
+
+        fn collapse_set(
+            &mut self,
+            _set: bevy::ecs::schedule::NodeId,
+            _systems: &[bevy::ecs::schedule::NodeId],
+            _dependency_flattened: &bevy::ecs::schedule::graph::DiGraph,
+        ) -> impl Iterator<Item = (bevy::ecs::schedule::NodeId, bevy::ecs::schedule::NodeId)> {
+            std::iter::empty()
+        }
+    };
+}
+
+pub(crate) use build_only_pass;
+
+/// System set marking systems whose auto-sets are inferred from their access by
+/// [`AccessFlowPass`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccessInferred;
+
+/// Extension trait for systems allowing to infer auto-sets from their access instead of their
+/// signature.
+pub trait InferAccessFlow<S, M>: IntoScheduleConfigs<S, M>
+where
+    S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+{
+    /// Infers auto-sets from the component and resource access that the system reports after
+    /// initialization. It works for every system, including boxed ones, systems built with
+    /// `SystemParamBuilder` and ones using third-party parameters, but requires
+    /// [`AccessFlowPass`] to be added to the schedule.
+    ///
+    /// Only actual access is taken into account, so filters like `With<T>` do not put the system
+    /// in `Reads<T>`. A system that may read or write anything is treated as a member of
+    /// [`ReadsAll`][crate::markers::ReadsAll] or [`WritesAll`][crate::markers::WritesAll].
+    /// Access to the `Events<E>` resource counts as access to `E` for events in an
+    /// [`EventFlow`][crate::markers::EventFlow] set of the schedule.
+    ///
+    /// Introspection, reports and other passes of this crate see the inferred auto-sets like the
+    /// ones added by [`.in_auto_sets()`][crate::automagic::InferFlow::in_auto_sets]. Since access
+    /// is only known after initialization, they are seen once the schedule is built.
+    fn in_access_sets(self) -> ScheduleConfigs<S> {
+        self.in_set(AccessInferred)
+    }
+}
+
+impl<M, S, I> InferAccessFlow<S, M> for I
+where
+    S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+    I: IntoScheduleConfigs<S, M>,
+{
+}
+
+/// Schedule build pass ordering systems marked with
+/// [`.in_access_sets()`][InferAccessFlow::in_access_sets] according to constraints on the
+/// auto-sets inferred from their access.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # let mut app = App::new();
+/// app.edit_schedule(Update, |schedule| {
+///     schedule.add_build_pass(AccessFlowPass);
+/// });
+/// ```
+#[derive(Debug, Default)]
+pub struct AccessFlowPass;
+
+impl ScheduleBuildPass for AccessFlowPass {
+    build_only_pass!();
+
+    fn build(
+        &mut self,
+        world: &mut World,
+        graph: &mut ScheduleGraph,
+        dependency_flattened: &mut DiGraph,
+    ) -> Result<(), ScheduleBuildError> {
+        add_access_flow(world, graph, dependency_flattened);
+        Ok(())
+    }
+}
+
+/// System set marking schedules sorted by [`FlowSortingPass`], so every system in them is treated
+/// as a member of the auto-sets inferred from its access.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct FlowSorted;
+
+/// Schedule build pass ordering every system in the schedule according to constraints on the
+/// auto-sets inferred from its access, without any annotation on the systems.
+///
+/// See [`.in_access_sets()`][InferAccessFlow::in_access_sets] for details about the inference.
+/// The pass is added by [`FlowSortingPlugin`], which also marks the schedule as sorted, so the
+/// inferred auto-sets are seen by introspection, reports and other passes of this crate.
+#[derive(Debug)]
+pub struct FlowSortingPass(());
+
+impl ScheduleBuildPass for FlowSortingPass {
+    build_only_pass!();
+
+    fn build(
+        &mut self,
+        world: &mut World,
+        graph: &mut ScheduleGraph,
+        dependency_flattened: &mut DiGraph,
+    ) -> Result<(), ScheduleBuildError> {
+        add_access_flow(world, graph, dependency_flattened);
+        Ok(())
+    }
+}
+
+/// Plugin adding [`FlowSortingPass`] to the given schedules, so constraints like
+/// [`write_before_read`][crate::ordering::write_before_read] apply to all their systems.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # let mut app = App::new();
+/// app.add_plugins(FlowSortingPlugin::new(Update).with_schedule(FixedUpdate));
+/// ```
+pub struct FlowSortingPlugin {
+    schedules: Vec<InternedScheduleLabel>,
+}
+
+#[allow(clippy::needless_pass_by_value)]
+impl FlowSortingPlugin {
+    /// Creates the plugin enabling flow sorting in `schedule`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedules: vec![schedule.intern()],
+        }
+    }
+
+    /// Enables flow sorting in `schedule` too.
+    #[must_use]
+    pub fn with_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedules.push(schedule.intern());
+        self
+    }
+}
+
+impl Plugin for FlowSortingPlugin {
+    fn build(&self, app: &mut App) {
+        for &schedule in &self.schedules {
+            app.configure_sets(schedule, FlowSorted)
+                .edit_schedule(schedule, |schedule| {
+                    schedule.add_build_pass(FlowSortingPass(()));
+                });
+        }
+    }
+
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
+
+/// Flow sets inferred from access by the last build of their schedule, keyed by the address of
+/// the access of the system, with the name of the system. It lets the flow of built schedules be
+/// read without the world, which is needed to match components with flow sets.
+static RECORDED_FLOWS: LazyLock<Mutex<RecordedFlows>> = LazyLock::new(Mutex::default);
+
+type RecordedFlows = HashMap<usize, (String, Vec<AnyFlowSet>)>;
+
+/// Returns a filter telling if auto-sets of a system in `graph` are inferred from its access:
+/// every system of schedules sorted by [`FlowSortingPass`] and members of [`AccessInferred`].
+pub(crate) fn access_inferred(graph: &ScheduleGraph) -> impl Fn(NodeId) -> bool {
+    let mut sorted = false;
+    let mut members = HashSet::new();
+    for (id, set, _) in graph.system_sets() {
+        let any = set.as_dyn_eq().as_any();
+        if any.is::<FlowSorted>() {
+            sorted = true;
+        } else if any.is::<AccessInferred>() {
+            members.extend(systems_in_set(graph, id));
+        }
+    }
+    move |system| sorted || members.contains(&system)
+}
+
+/// Returns the flow sets that systems of `graph` belong to according to their access, keyed by
+/// the system, for systems whose auto-sets are inferred from access. The result is recorded, so
+/// [`recorded_access_flows`] returns it after the schedule is built.
+pub(crate) fn access_flows(
+    world: &World,
+    graph: &ScheduleGraph,
+) -> HashMap<NodeId, Vec<AnyFlowSet>> {
+    let is_inferred = access_inferred(graph);
+    let event_targets = event_resources(graph, world.components());
+    let mut recorded = RECORDED_FLOWS.lock().unwrap();
+    graph
+        .systems()
+        .filter(|&(id, _, _)| is_inferred(id))
+        .map(|(id, system, _)| {
+            let flows = system_flows(world, system, &event_targets);
+            recorded.insert(
+                access_address(system),
+                (system.name().into_owned(), flows.clone()),
+            );
+            (id, flows)
+        })
+        .collect()
+}
+
+/// Returns the flow sets inferred from access of `system` by the last build of its schedule.
+pub(crate) fn recorded_access_flows(system: &ScheduleSystem) -> Vec<AnyFlowSet> {
+    let recorded = RECORDED_FLOWS.lock().unwrap();
+    match recorded.get(&access_address(system)) {
+        Some((name, flows)) if *name == system.name() => flows.clone(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the address of the access of `system`, which stays the same when the system is moved
+/// or wrapped in another one.
+fn access_address(system: &ScheduleSystem) -> usize {
+    std::ptr::from_ref(system.component_access()).addr()
+}
+
+/// Returns the flow sets that `system` belongs to according to its access.
+fn system_flows(
+    world: &World,
+    system: &ScheduleSystem,
+    event_targets: &HashMap<ComponentId, FlowTarget>,
+) -> Vec<AnyFlowSet> {
+    let access = system.component_access();
+    let mut flows = Vec::new();
+    if system.is_exclusive() || access.has_write_all() {
+        flows.push(AnyFlowSet::All(FlowAccess::Write));
+    } else if access.has_read_all() {
+        flows.push(AnyFlowSet::All(FlowAccess::Read));
+    }
+    for (component, flow_access) in accessed_components(access) {
+        for target in component_targets(world, component, event_targets) {
+            // Like with `.in_auto_sets()`, mutable access puts systems in `Modifies`, except for
+            // events, which are only ever written.
+            let is_event = event_targets.get(&component) == Some(&target);
+            let flow_access = if flow_access == FlowAccess::Write && !is_event {
+                FlowAccess::Modify
+            } else {
+                flow_access
+            };
+            flows.push(AnyFlowSet::Flow(FlowSet {
+                access: flow_access,
+                target,
+                name: None,
+            }));
+        }
+    }
+    flows
+}
+
+/// Adds edges to `dependency_flattened`, so systems whose auto-sets are inferred from their access
+/// are ordered as if they were members of these sets.
+pub(crate) fn add_access_flow(
+    world: &World,
+    graph: &ScheduleGraph,
+    dependency_flattened: &mut DiGraph,
+) {
+    let flow = FlowGraph::extract_in(world, graph);
+    let mut accessed = HashMap::<NodeId, Vec<NodeId>>::new();
+    for &(system, set) in &flow.accessed {
+        accessed.entry(set).or_default().push(system);
+    }
+    if accessed.is_empty() {
+        return;
+    }
+
+    // Constraints nest `WritesAll` and `ReadsAll` in typed sets, so members of nested sets are
+    // members of the outer set too.
+    let accessed_sets = |node: NodeId| -> Vec<NodeId> {
+        let mut sets = sets_in_set(graph, node);
+        sets.push(node);
+        sets.retain(|set| accessed.contains_key(set));
+        sets
+    };
+    let members = |node: NodeId| -> Vec<NodeId> {
+        if node.is_system() {
+            return vec![node];
+        }
+        let mut members = systems_in_set(graph, node);
+        for set in accessed_sets(node) {
+            members.extend(&accessed[&set]);
+        }
+        members
+    };
+
+    for (from, to) in graph.dependency().graph().all_edges() {
+        if accessed_sets(from).is_empty() && accessed_sets(to).is_empty() {
+            continue;
+        }
+        let successors = members(to);
+        for before in members(from) {
+            for &after in &successors {
+                if before != after {
+                    dependency_flattened.add_edge(before, after);
+                }
+            }
+        }
+    }
+}
+
+/// Returns all targets of flow sets that access to `component` corresponds to. Access to the
+/// `Events<E>` resource of an event in `event_targets` corresponds to flow sets of `E` too.
+pub(crate) fn component_targets(
+    world: &World,
+    component: ComponentId,
+    event_targets: &HashMap<ComponentId, FlowTarget>,
+) -> Vec<FlowTarget> {
+    [FlowTarget::component(world.components(), component)]
+        .into_iter()
+        .chain(event_targets.get(&component).copied())
+        .collect()
+}
+
+/// Returns the components and resources from `access` with the kind of the access. Archetypal
+/// access, like the one of `Has<T>`, only checks for presence of a component, so it is skipped.
+pub(crate) fn accessed_components(access: &Access<ComponentId>) -> Vec<(ComponentId, FlowAccess)> {
+    let mut components = access
+        .resource_reads()
+        .map(|id| (id, FlowAccess::Read))
+        .chain(access.resource_writes().map(|id| (id, FlowAccess::Write)))
+        .collect::<Vec<_>>();
+    if let Ok(component_access) = access.try_iter_component_access() {
+        components.extend(component_access.filter_map(|kind| match kind {
+            ComponentAccessKind::Archetypal(_) => None,
+            ComponentAccessKind::Shared(id) => Some((id, FlowAccess::Read)),
+            ComponentAccessKind::Exclusive(id) => Some((id, FlowAccess::Write)),
+        }));
+    }
+    components
+}
+
+/// Returns all systems belonging to `set`, directly or through its subsets.
+pub(crate) fn systems_in_set(graph: &ScheduleGraph, set: NodeId) -> Vec<NodeId> {
+    descendants(graph, set)
+        .into_iter()
+        .filter(NodeId::is_system)
+        .collect()
+}
+
+/// Returns all sets nested in `set`, directly or through other sets.
+pub(crate) fn sets_in_set(graph: &ScheduleGraph, set: NodeId) -> Vec<NodeId> {
+    descendants(graph, set)
+        .into_iter()
+        .filter(NodeId::is_set)
+        .collect()
+}
+
+/// Returns all systems and sets below `set` in the hierarchy.
+fn descendants(graph: &ScheduleGraph, set: NodeId) -> Vec<NodeId> {
+    let mut nodes = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![set];
+    while let Some(node) = stack.pop() {
+        for child in graph.hierarchy().graph().neighbors(node) {
+            if !visited.insert(child) {
+                continue;
+            }
+            nodes.push(child);
+            if child.is_set() {
+                stack.push(child);
+            }
+        }
+    }
+    nodes
+}
@@ -0,0 +1,397 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    ecs::{
+        schedule::{LogLevel, ScheduleBuildSettings},
+        system::ScheduleSystem,
+    },
+    prelude::*,
+};
+
+use super::{AccessFlowPass, FlowSortingPlugin, InferAccessFlow};
+use crate::prelude::{
+    read_before_write, write_before_read, write_before_read_dyn, EventFlow, FlowIntrospection,
+    FlowTarget, InferFlow, WritesAll,
+};
+
+#[test]
+fn resource_access_is_ordered() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        add_pass(&mut app);
+        app.init_resource::<Counter>();
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_counter: Res<Counter>| trace_handle.lock().unwrap().push("read"))
+                .in_access_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_counter: ResMut<Counter>| trace_handle.lock().unwrap().push("write"))
+                .in_access_sets(),
+        );
+
+        app.configure_sets(Startup, write_before_read::<Counter>());
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["write", "read"]);
+    }
+}
+
+#[test]
+fn component_access_is_ordered() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        add_pass(&mut app);
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_query: Query<&mut Health>| trace_handle.lock().unwrap().push("write"))
+                .in_access_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_query: Query<&Health>| trace_handle.lock().unwrap().push("read"))
+                .in_access_sets(),
+        );
+
+        app.configure_sets(Startup, read_before_write::<Health>());
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["read", "write"]);
+    }
+}
+
+#[test]
+fn event_access_is_ordered() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        add_pass(&mut app);
+        app.add_event::<Hit>();
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_reader: EventReader<Hit>| trace_handle.lock().unwrap().push("read"))
+                .in_access_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_writer: EventWriter<Hit>| trace_handle.lock().unwrap().push("write"))
+                .in_access_sets(),
+        );
+
+        app.configure_sets(
+            Startup,
+            (EventFlow::of::<Hit>(), write_before_read::<Hit>()),
+        );
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["write", "read"]);
+    }
+}
+
+#[test]
+fn event_access_matches_inferred_readers() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        add_pass(&mut app);
+        app.add_event::<Hit>();
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_reader: EventReader<Hit>| trace_handle.lock().unwrap().push("read"))
+                .in_auto_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_writer: EventWriter<Hit>| trace_handle.lock().unwrap().push("write"))
+                .in_access_sets(),
+        );
+
+        app.configure_sets(Startup, write_before_read::<Hit>());
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["write", "read"]);
+    }
+}
+
+#[test]
+fn presence_checks_are_not_reads() {
+    let mut app = App::new();
+    add_pass(&mut app);
+    app.add_systems(
+        Startup,
+        (
+            (|_query: Query<Has<Health>>| {}).in_access_sets(),
+            (|_query: Query<&mut Health>| {}).in_access_sets(),
+        )
+            .chain(),
+    )
+    .configure_sets(Startup, write_before_read::<Health>());
+    app.run();
+}
+
+#[test]
+fn boxed_system_mixes_with_signature_inference() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        add_pass(&mut app);
+        app.init_resource::<Counter>();
+
+        let trace_handle = Arc::clone(&trace);
+        let boxed: ScheduleSystem = Box::new(IntoSystem::into_system(
+            (move |_counter: ResMut<Counter>| trace_handle.lock().unwrap().push("write")).map(Ok),
+        ));
+        app.add_systems(Startup, boxed.in_access_sets());
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_counter: Res<Counter>| trace_handle.lock().unwrap().push("read"))
+                .in_auto_sets(),
+        );
+
+        app.configure_sets(Startup, write_before_read::<Counter>());
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["write", "read"]);
+    }
+}
+
+#[test]
+fn exclusive_system_is_in_writes_all() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        add_pass(&mut app);
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_world: &mut World| trace_handle.lock().unwrap().push("exclusive"))
+                .in_access_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_query: Query<&Health>| trace_handle.lock().unwrap().push("read"))
+                .in_access_sets()
+                .before(WritesAll),
+        );
+
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["read", "exclusive"]);
+    }
+}
+
+#[test]
+fn exclusive_system_is_ordered_by_constraints() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        add_pass(&mut app);
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_query: Query<&Health>| trace_handle.lock().unwrap().push("read"))
+                .in_access_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_world: &mut World| trace_handle.lock().unwrap().push("exclusive"))
+                .in_access_sets(),
+        );
+
+        app.configure_sets(Startup, write_before_read::<Health>());
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["exclusive", "read"]);
+    }
+}
+
+#[test]
+fn component_id_sets_are_ordered() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        add_pass(&mut app);
+        let health = app.world_mut().register_component::<Health>();
+        let health = FlowTarget::component(app.world().components(), health);
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_query: Query<&Health>| trace_handle.lock().unwrap().push("read"))
+                .in_access_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_query: Query<&mut Health>| trace_handle.lock().unwrap().push("write"))
+                .in_access_sets(),
+        );
+
+        app.configure_sets(Startup, write_before_read_dyn(health));
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["write", "read"]);
+    }
+}
+
+#[test]
+fn component_id_sets_order_inferred_systems() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        add_pass(&mut app);
+        let health = app.world_mut().register_component::<Health>();
+        let health = FlowTarget::component(app.world().components(), health);
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_query: Query<&Health>| trace_handle.lock().unwrap().push("read"))
+                .in_auto_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move |_query: Query<&mut Health>| trace_handle.lock().unwrap().push("modify"))
+                .in_auto_sets(),
+        );
+
+        app.configure_sets(Startup, write_before_read_dyn(health));
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["modify", "read"]);
+    }
+}
+
+#[test]
+fn plugin_sorts_unannotated_systems() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        app.add_plugins(FlowSortingPlugin::new(Startup).with_schedule(Update));
+        deny_ambiguities(&mut app);
+        app.init_resource::<Counter>().add_event::<Hit>();
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            move |_reader: EventReader<Hit>, _query: Query<&mut Health>| {
+                trace_handle.lock().unwrap().push("third");
+            },
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            move |_writer: EventWriter<Hit>, _counter: Res<Counter>| {
+                trace_handle.lock().unwrap().push("second");
+            },
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(Startup, move |_counter: ResMut<Counter>| {
+            trace_handle.lock().unwrap().push("first");
+        });
+
+        app.configure_sets(
+            Startup,
+            (
+                write_before_read::<Counter>(),
+                EventFlow::of::<Hit>(),
+                write_before_read::<Hit>(),
+            ),
+        );
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["first", "second", "third"]);
+    }
+}
+
+#[test]
+fn sorted_flow_is_introspected_after_build() {
+    fn modify_counter(_counter: ResMut<Counter>) {}
+    fn read_counter(_counter: Res<Counter>) {}
+
+    let mut app = App::new();
+    app.add_plugins(FlowSortingPlugin::new(Update));
+    app.init_resource::<Counter>();
+    app.add_systems(Update, (modify_counter, read_counter));
+    app.configure_sets(Update, write_before_read::<Counter>());
+    app.update();
+
+    let schedule = app.get_schedule(Update).unwrap();
+    let writers = schedule.writers_of::<Counter>();
+    let readers = schedule.readers_of::<Counter>();
+    assert_eq!(writers.len(), 1);
+    assert!(writers[0].name.ends_with("modify_counter"));
+    assert_eq!(readers.len(), 1);
+    assert!(readers[0].name.ends_with("read_counter"));
+    assert!(schedule
+        .flows_of(readers[0].id)
+        .iter()
+        .all(|flow| flow.inferred));
+}
+
+fn add_pass(app: &mut App) {
+    app.edit_schedule(Startup, |schedule| {
+        schedule.add_build_pass(AccessFlowPass);
+    });
+    deny_ambiguities(app);
+}
+
+fn deny_ambiguities(app: &mut App) {
+    app.edit_schedule(Startup, |schedule| {
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: LogLevel::Error,
+            ..default()
+        });
+    });
+}
+
+#[derive(Resource, Default)]
+struct Counter;
+
+#[derive(Component)]
+struct Health;
+
+#[derive(Event)]
+struct Hit;
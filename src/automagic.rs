@@ -1,6 +1,5 @@
-use std::marker::PhantomData;
+use std::{any::TypeId, marker::PhantomData};
 
-use bevy::prelude::{BevyError, System};
 use bevy::{
     diagnostic::Diagnostics,
     ecs::{
@@ -14,18 +13,22 @@ use bevy::{
         schedule::graph::GraphInfo,
         schedule::ScheduleConfigs,
         schedule::{Chain, Schedulable},
-        system::{DynSystemParam, SystemBuffer, SystemChangeTick, SystemName, SystemParam},
+        system::{
+            DynSystemParam, ExclusiveSystemParamFunction, InfallibleSystemWrapper, IntoPipeSystem,
+            ScheduleSystem, System, SystemBuffer, SystemChangeTick, SystemName, SystemParam,
+        },
         world::{
             DeferredWorld, EntityMutExcept, EntityRefExcept, FilteredEntityMut, FilteredEntityRef,
             WorldId,
         },
     },
     prelude::{
-        Added, AnyOf, Bundle, Changed, Commands, Component, Deferred, Entity, EntityMut, EntityRef,
-        Event, EventReader, EventWriter, FilteredResources, FilteredResourcesMut, FromWorld, Has,
-        IntoScheduleConfigs, Local, MeshRayCast, Mut, NonSend, NonSendMut, Or, ParallelCommands,
-        ParamSet, PickingEventWriters, Populated, Query, Ref, RemovedComponents, Res, ResMut,
-        Resource, Single, SystemParamFunction, TransformHelper, With, Without, World,
+        Added, AnyOf, BevyError, Bundle, Changed, Commands, Component, Deferred, Entity, EntityMut,
+        EntityRef, Event, EventReader, EventWriter, FilteredResources, FilteredResourcesMut,
+        FromWorld, Has, IntoScheduleConfigs, IntoSystem, Local, MeshRayCast, Mut, NonSend,
+        NonSendMut, Or, ParallelCommands, ParamSet, PickingEventWriters, Populated, Query, Ref,
+        RemovedComponents, Res, ResMut, Resource, Single, SystemParamFunction, SystemSet,
+        TransformHelper, With, Without, World,
     },
     render::{
         sync_world::{MainEntity, RenderEntity},
@@ -35,14 +38,37 @@ use bevy::{
 };
 use bevy_utils_proc_macros::all_tuples;
 
-use crate::prelude::IntoSystemRW;
+use crate::{
+    flow_graph::{is_ignored, is_produced, note_flow_set, record_ignored, record_inferred},
+    prelude::{EventFlow, IntoSystemRW, ReadsAll, WritesAll},
+};
 
 use impl_trait_for_tuples::impl_for_tuples;
 
 #[cfg(test)]
 mod tests;
 
-trait AutoSetArg {
+/// System parameter for which auto-sets can be inferred.
+///
+/// It is implemented for most of the parameters provided by Bevy. For your own
+/// [`SystemParam`] structs, use `#[derive(AutoSetArg)]`, which applies inference of every field.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # use bevy::ecs::system::SystemParam;
+/// # #[derive(Resource)]
+/// # struct Score;
+/// # #[derive(Event)]
+/// # struct KillEvent;
+/// #[derive(SystemParam, AutoSetArg)]
+/// struct Scoring<'w, 's> {
+///     score: ResMut<'w, Score>,
+///     kills: EventReader<'w, 's, KillEvent>,
+/// }
+/// ```
+pub trait AutoSetArg {
+    /// Adds the system to auto-sets inferred from this parameter.
     fn apply<S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>;
@@ -53,7 +79,7 @@ impl<E: Event> AutoSetArg for EventReader<'_, '_, E> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<E>()
+        reads::<E, S>(sys).in_set(EventFlow::of::<E>())
     }
 }
 
@@ -62,7 +88,7 @@ impl<E: Event> AutoSetArg for EventWriter<'_, E> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.writes::<E>()
+        writes::<E, S>(sys).in_set(EventFlow::of::<E>())
     }
 }
 
@@ -71,7 +97,7 @@ impl<R: Resource> AutoSetArg for Res<'_, R> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<R>()
+        reads::<R, S>(sys)
     }
 }
 
@@ -80,7 +106,7 @@ impl<R: Resource> AutoSetArg for ResMut<'_, R> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.writes::<R>()
+        mutates::<R, S>(sys)
     }
 }
 
@@ -89,7 +115,7 @@ impl<T: 'static> AutoSetArg for NonSend<'_, T> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<T>()
+        reads::<T, S>(sys)
     }
 }
 
@@ -98,7 +124,17 @@ impl<T: 'static> AutoSetArg for NonSendMut<'_, T> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.writes::<T>()
+        mutates::<T, S>(sys)
+    }
+}
+
+impl AutoSetArg for &World {
+    fn apply<S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
+    where
+        S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+    {
+        note_flow_set(ReadsAll.intern());
+        sys.in_set(ReadsAll)
     }
 }
 
@@ -111,7 +147,27 @@ impl<T: AutoSetArg> AutoSetArg for Option<T> {
     }
 }
 
-trait AutoSetArgInQuery {
+/// Query data for which auto-sets can be inferred. Immutable access to a component is treated as
+/// a read, and mutable access as a write.
+///
+/// For your own [`QueryData`] structs, use `#[derive(AutoSetArgInQuery)]`. If the struct is
+/// `#[query_data(mutable)]`, its generated `ReadOnly` variant is covered as well.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # use bevy::ecs::query::QueryData;
+/// # #[derive(Component)]
+/// # struct Health;
+/// #[derive(QueryData, AutoSetArgInQuery)]
+/// #[query_data(mutable)]
+/// struct PlayerQuery {
+///     transform: &'static mut Transform,
+///     health: &'static Health,
+/// }
+/// ```
+pub trait AutoSetArgInQuery {
+    /// Adds the system to auto-sets inferred from this query data.
     fn apply<S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>;
@@ -122,7 +178,7 @@ impl<T: Component> AutoSetArgInQuery for &T {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<T>()
+        reads::<T, S>(sys)
     }
 }
 
@@ -131,7 +187,7 @@ impl<T: Component> AutoSetArgInQuery for Ref<'_, T> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<T>()
+        reads::<T, S>(sys)
     }
 }
 
@@ -140,7 +196,7 @@ impl<T: Component> AutoSetArgInQuery for &mut T {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.writes::<T>()
+        mutates::<T, S>(sys)
     }
 }
 
@@ -149,7 +205,7 @@ impl<T: Component> AutoSetArgInQuery for Mut<'_, T> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.writes::<T>()
+        mutates::<T, S>(sys)
     }
 }
 
@@ -158,7 +214,7 @@ impl<T: Component> AutoSetArgInQuery for Has<T> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<T>()
+        reads::<T, S>(sys)
     }
 }
 
@@ -185,7 +241,7 @@ impl AutoSetArgInQuery for NameOrEntity {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<Name>()
+        reads::<Name, S>(sys)
     }
 }
 
@@ -336,7 +392,27 @@ impl AutoSetArgInQuery for Tuple {
     }
 }
 
-trait AutoSetArgInQueryFilter {
+/// Query filter for which auto-sets can be inferred. Every component mentioned in a filter is
+/// treated as a read.
+///
+/// For your own [`QueryFilter`] structs, use `#[derive(AutoSetArgInQueryFilter)]`.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # use bevy::ecs::query::QueryFilter;
+/// # #[derive(Component)]
+/// # struct Enemy;
+/// # #[derive(Component)]
+/// # struct Dead;
+/// #[derive(QueryFilter, AutoSetArgInQueryFilter)]
+/// struct EnemyFilter {
+///     with: With<Enemy>,
+///     without: Without<Dead>,
+/// }
+/// ```
+pub trait AutoSetArgInQueryFilter {
+    /// Adds the system to auto-sets inferred from this query filter.
     fn apply<S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>;
@@ -356,7 +432,7 @@ impl<C: Component> AutoSetArgInQueryFilter for Added<C> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<C>()
+        reads::<C, S>(sys)
     }
 }
 
@@ -365,7 +441,7 @@ impl<C: Component> AutoSetArgInQueryFilter for Changed<C> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<C>()
+        reads::<C, S>(sys)
     }
 }
 
@@ -374,7 +450,7 @@ impl<C: Component> AutoSetArgInQueryFilter for With<C> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<C>()
+        reads::<C, S>(sys)
     }
 }
 
@@ -383,7 +459,7 @@ impl<C: Component> AutoSetArgInQueryFilter for Without<C> {
     where
         S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
     {
-        sys.reads::<C>()
+        reads::<C, S>(sys)
     }
 }
 
@@ -596,8 +672,6 @@ impl<T> NoInfer for PhantomData<T> {}
 
 impl<T> NoInfer for Vec<T> {}
 
-impl NoInfer for &World {}
-
 impl NoInfer for Diagnostics<'_, '_> {}
 
 impl NoInfer for DefaultUiCamera<'_, '_> {}
@@ -667,9 +741,126 @@ impl AutoSetArg for Tuple {
     }
 }
 
-/// System for which auto-sets ([Writes] and [Reads]) can be inferred.
+/// Adds the system to [`Reads<T>`][crate::markers::Reads], unless `T` is left out of inference.
+fn reads<T: 'static, S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
+where
+    S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+{
+    if is_ignored(TypeId::of::<T>()) {
+        sys
+    } else {
+        sys.reads::<T>()
+    }
+}
+
+/// Adds the system to [`Writes<T>`][crate::markers::Writes], unless `T` is left out of inference.
+fn writes<T: 'static, S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
+where
+    S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+{
+    if is_ignored(TypeId::of::<T>()) {
+        sys
+    } else {
+        sys.writes::<T>()
+    }
+}
+
+/// Adds the system to [`Modifies<T>`][crate::markers::Modifies], or to
+/// [`Writes<T>`][crate::markers::Writes] if it was declared to produce `T`. Does nothing if `T`
+/// is left out of inference.
+fn mutates<T: 'static, S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
+where
+    S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+{
+    if is_ignored(TypeId::of::<T>()) {
+        sys
+    } else if is_produced(TypeId::of::<T>()) {
+        sys.writes::<T>()
+    } else {
+        sys.modifies::<T>()
+    }
+}
+
+/// Tuple of types whose flow is left out of inference by
+/// [`.in_auto_sets_except()`][InferFlow::in_auto_sets_except].
+pub trait IgnoredTypes {
+    /// Returns ids of the types.
+    fn type_ids() -> Vec<TypeId>;
+}
+
+macro_rules! impl_ignored_types {
+    ( $($ty: ident),* ) => {
+        impl<$($ty: 'static),*> IgnoredTypes for ($($ty,)*) {
+            fn type_ids() -> Vec<TypeId> {
+                vec![$( TypeId::of::<$ty>() ),*]
+            }
+        }
+    };
+}
+
+all_tuples!(impl_ignored_types, 1, 15, T);
+
+/// System (or a composition of systems) whose signature can be analyzed to infer auto-sets.
+pub trait InferableSystem<Marker> {
+    /// Adds `sys` to the auto-sets inferred from the signature.
+    fn apply<S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
+    where
+        S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>;
+}
+
+#[doc(hidden)]
+pub struct IsFunctionSystem;
+
+impl<T, M> InferableSystem<(IsFunctionSystem, M)> for T
+where
+    T::Param: AutoSetArg,
+    T: SystemParamFunction<M>,
+{
+    fn apply<S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
+    where
+        S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+    {
+        <T::Param as AutoSetArg>::apply(sys)
+    }
+}
+
+#[doc(hidden)]
+pub struct IsExclusiveSystem;
+
+impl<T, M> InferableSystem<(IsExclusiveSystem, M)> for T
+where
+    T: ExclusiveSystemParamFunction<M>,
+{
+    fn apply<S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
+    where
+        S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+    {
+        note_flow_set(WritesAll.intern());
+        sys.in_set(WritesAll)
+    }
+}
+
+#[doc(hidden)]
+pub struct IsPipeSystem;
+
+impl<A, B, MA, MB> InferableSystem<(IsPipeSystem, MA, MB)> for IntoPipeSystem<A, B>
+where
+    A: InferableSystem<MA>,
+    B: InferableSystem<MB>,
+{
+    fn apply<S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
+    where
+        S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+    {
+        let sys = A::apply(sys);
+        B::apply(sys)
+    }
+}
+
+/// System for which auto-sets ([Writes], [Modifies] and [Reads]) can be inferred.
 ///
 /// [Writes]: crate::prelude::Writes
+/// [Modifies]: crate::prelude::Modifies
 /// [Reads]: crate::prelude::Reads
 pub trait InferFlow<S, Marker>
 where
@@ -680,20 +871,61 @@ where
     /// the signature, you need to specify [Writes] marker manually in order to use constraint
     /// based on this resource or component.
     ///
+    /// For piped systems, auto-sets of both sides of the pipe are combined. Fallible systems,
+    /// returning `Result<(), E>` where `E` converts into [`BevyError`], are supported as well.
+    /// Exclusive systems are added to [`WritesAll`], and systems taking `&World` to [`ReadsAll`].
+    /// Systems taking input can't be added to a schedule, but the systems running them can be put
+    /// in the same sets with [`.in_auto_sets_of()`][crate::prelude::InferFlowOf::in_auto_sets_of].
+    ///
     /// [Writes]: crate::prelude::Writes
     fn in_auto_sets(self) -> ScheduleConfigs<S>;
+
+    /// Infers auto-sets for a system like [`.in_auto_sets()`][Self::in_auto_sets], but leaves out
+    /// the [`Reads`][crate::prelude::Reads], [`Writes`][crate::prelude::Writes] and
+    /// [`Modifies`][crate::prelude::Modifies] sets of each type in the tuple `F`. It is useful for
+    /// systems that deliberately see the value from the previous frame, which constraints on those
+    /// sets would otherwise order, or put in a cycle.
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_sorting::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Velocity;
+    /// # fn predict_collisions(velocities: Query<&Velocity>) {}
+    /// # let mut app = App::new();
+    /// app.add_systems(Update, predict_collisions.in_auto_sets_except::<(Velocity,)>());
+    /// ```
+    fn in_auto_sets_except<F: IgnoredTypes>(self) -> ScheduleConfigs<S>
+    where
+        Self: Sized,
+    {
+        record_ignored(&F::type_ids(), || self.in_auto_sets())
+    }
 }
 
-impl<T, M> InferFlow<Box<(dyn System<In = (), Out = Result<(), BevyError>> + 'static)>, M> for T
+impl<T, M, SM> InferFlow<ScheduleSystem, (M, SM)> for T
 where
-    T::Param: AutoSetArg,
-    T: SystemParamFunction<M, In = (), Out = ()>,
-    M: 'static,
+    T: InferableSystem<M> + IntoSystem<(), (), SM>,
+{
+    fn in_auto_sets(self) -> ScheduleConfigs<ScheduleSystem> {
+        // Boxed like `add_systems` does, so the recorded type is the one seen in the schedule.
+        let system: ScheduleSystem =
+            Box::new(InfallibleSystemWrapper::new(IntoSystem::into_system(self)));
+        record_inferred(System::type_id(&*system), || {
+            T::apply(system.into_configs())
+        })
+    }
+}
+
+impl<T, M, SM, E> InferFlow<ScheduleSystem, (M, SM, E)> for T
+where
+    T: InferableSystem<M> + IntoSystem<(), Result<(), E>, SM>,
+    E: Into<BevyError> + 'static,
 {
-    fn in_auto_sets(
-        self,
-    ) -> ScheduleConfigs<Box<(dyn System<In = (), Out = Result<(), BevyError>> + 'static)>> {
-        <T::Param as AutoSetArg>::apply(self.into_configs())
+    fn in_auto_sets(self) -> ScheduleConfigs<ScheduleSystem> {
+        let system =
+            IntoSystem::into_system(self.map(|result: Result<(), E>| result.map_err(Into::into)));
+        record_inferred(System::type_id(&system), || T::apply(system.into_configs()))
     }
 }
 
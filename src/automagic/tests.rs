@@ -1,12 +1,25 @@
-use std::{any, collections::HashMap, vec};
+use std::{
+    any::{self, TypeId},
+    collections::HashMap,
+    vec,
+};
 
 use assert_unordered::assert_eq_unordered_sort;
 use bevy::{
-    ecs::schedule::{NodeId, ScheduleGraph},
+    ecs::{
+        query::{QueryData, QueryFilter},
+        schedule::{NodeId, ScheduleGraph},
+        system::SystemParam,
+    },
     prelude::*,
 };
 
 use super::{InferFlow, InferFlowEach};
+use crate::flow_graph::record_ignored;
+use crate::prelude::{
+    AutoSetArg, AutoSetArgInQuery, AutoSetArgInQueryFilter, ExclusiveFlow, InferFlowOf,
+    IntoSystemProducing, IntoSystemRW,
+};
 
 #[test]
 fn simple_event_sorting() {
@@ -115,8 +128,9 @@ fn commands_do_not_create_autoset() {
         ),
     );
 
+    // System type sets of both systems, `Reads<SomeEvent>` and `EventFlow` of `SomeEvent`.
     let graph = app.get_schedule(Update).unwrap().graph();
-    assert_eq!(graph.system_sets().count(), 3);
+    assert_eq!(graph.system_sets().count(), 4);
 }
 
 #[test]
@@ -131,7 +145,7 @@ fn simple_resources_sorting() {
     );
 
     let graph = app.get_schedule(Update).unwrap().graph();
-    let (read_something_set, write_something_set) = find_set_pair(graph, "Something");
+    let (read_something_set, write_something_set) = find_modify_pair(graph, "Something");
     let res_system = find_system(graph, &resource_only);
     let res_mut_system = find_system(graph, &resource_mut_only);
 
@@ -140,7 +154,7 @@ fn simple_resources_sorting() {
     assert_eq_unordered_sort!(
         vec![res_mut_system],
         systems_for_set(graph, write_something_set)
-    )
+    );
 }
 
 #[test]
@@ -156,8 +170,8 @@ fn mixed_resource_sorting() {
     );
 
     let graph = app.get_schedule(Update).unwrap().graph();
-    let (read_something_set, write_something_set) = find_set_pair(graph, "Something");
-    let (read_other_set, write_other_set) = find_set_pair(graph, "SomethingElse");
+    let (read_something_set, write_something_set) = find_modify_pair(graph, "Something");
+    let (read_other_set, write_other_set) = find_modify_pair(graph, "SomethingElse");
     let something_mut_system = find_system(graph, &resource_mut_only);
     let mixed_system = find_system(graph, &resource_mixed);
     let other_system = find_system(graph, &other_res_only);
@@ -184,14 +198,14 @@ fn queries_create_autosets() {
 
     let graph = app.get_schedule(Update).unwrap().graph();
     let read_some_data_set = find_set(graph, "Reads(\"SomeData\")");
-    let write_other_data_set = find_set(graph, "Writes(\"OtherData\")");
+    let write_other_data_set = find_set(graph, "Modifies(\"OtherData\")");
     let system = find_system(graph, &with_query);
 
     assert_eq!(3, graph.system_sets().count());
 
     assert_eq_unordered_sort!(vec![system], systems_for_set(graph, read_some_data_set));
 
-    assert_eq_unordered_sort!(vec![system], systems_for_set(graph, write_other_data_set))
+    assert_eq_unordered_sort!(vec![system], systems_for_set(graph, write_other_data_set));
 }
 
 #[test]
@@ -223,10 +237,10 @@ fn big_system_test() {
 
     let graph = app.get_schedule(Update).unwrap().graph();
     let read_some_data_set = find_set(graph, "Reads(\"SomeData\")");
-    let write_other_data_set = find_set(graph, "Writes(\"OtherData\")");
-    let write_third_data_set = find_set(graph, "Writes(\"DataNumberThree\")");
+    let write_other_data_set = find_set(graph, "Modifies(\"OtherData\")");
+    let write_third_data_set = find_set(graph, "Modifies(\"DataNumberThree\")");
     let read_fourth_data_set = find_set(graph, "Reads(\"DataNumberFour\")");
-    let write_res_set = find_set(graph, "Writes(\"Something\")");
+    let write_res_set = find_set(graph, "Modifies(\"Something\")");
     let system = find_system(graph, &big_system);
 
     for set in [
@@ -240,6 +254,134 @@ fn big_system_test() {
     }
 }
 
+#[test]
+fn piped_system_combines_autosets() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (
+            produce_count.pipe(consume_count).in_auto_sets(),
+            produce_count
+                .pipe(forward_count)
+                .pipe(consume_count)
+                .in_auto_sets(),
+        ),
+    );
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let write_some_set = find_set(graph, "Writes(\"SomeEvent\")");
+    let read_something_set = find_set(graph, "Reads(\"Something\")");
+    let write_other_data_set = find_set(graph, "Modifies(\"OtherData\")");
+    let pipe_system = find_system_by_name(graph, "Pipe(");
+    let nested_pipe_system = find_system_by_name(graph, "Pipe(Pipe(");
+
+    assert_eq_unordered_sort!(
+        vec![pipe_system, nested_pipe_system],
+        systems_for_set(graph, write_some_set)
+    );
+    assert_eq_unordered_sort!(
+        vec![pipe_system, nested_pipe_system],
+        systems_for_set(graph, read_something_set)
+    );
+    assert_eq_unordered_sort!(
+        vec![nested_pipe_system],
+        systems_for_set(graph, write_other_data_set)
+    );
+}
+
+#[test]
+fn derived_system_param_creates_autosets() {
+    let mut app = App::new();
+    app.add_systems(Update, with_custom_param.in_auto_sets());
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let read_some_event_set = find_set(graph, "Reads(\"SomeEvent\")");
+    let write_something_set = find_set(graph, "Modifies(\"Something\")");
+    let read_some_data_set = find_set(graph, "Reads(\"SomeData\")");
+    let write_other_data_set = find_set(graph, "Modifies(\"OtherData\")");
+    let system = find_system(graph, &with_custom_param);
+
+    for set in [
+        read_some_event_set,
+        write_something_set,
+        read_some_data_set,
+        write_other_data_set,
+    ] {
+        assert_eq_unordered_sort!(vec![system], systems_for_set(graph, set));
+    }
+}
+
+#[test]
+fn derived_query_data_creates_autosets() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (
+            with_custom_query.in_auto_sets(),
+            with_read_only_custom_query.in_auto_sets(),
+        ),
+    );
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let (read_some_data_set, write_some_data_set) = find_modify_pair(graph, "SomeData");
+    let read_other_data_set = find_set(graph, "Reads(\"OtherData\")");
+    let read_third_data_set = find_set(graph, "Reads(\"DataNumberThree\")");
+    let system = find_system(graph, &with_custom_query);
+    let read_only_system = find_system(graph, &with_read_only_custom_query);
+
+    assert_eq_unordered_sort!(vec![system], systems_for_set(graph, write_some_data_set));
+    assert_eq_unordered_sort!(
+        vec![read_only_system],
+        systems_for_set(graph, read_some_data_set)
+    );
+    for set in [read_other_data_set, read_third_data_set] {
+        assert_eq_unordered_sort!(vec![system, read_only_system], systems_for_set(graph, set));
+    }
+}
+
+#[test]
+fn derived_query_filter_creates_autosets() {
+    let mut app = App::new();
+    app.add_systems(Update, with_custom_filter.in_auto_sets());
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let read_some_data_set = find_set(graph, "Reads(\"SomeData\")");
+    let read_other_data_set = find_set(graph, "Reads(\"OtherData\")");
+    let read_third_data_set = find_set(graph, "Reads(\"DataNumberThree\")");
+    let read_fourth_data_set = find_set(graph, "Reads(\"DataNumberFour\")");
+    let system = find_system(graph, &with_custom_filter);
+
+    for set in [
+        read_some_data_set,
+        read_other_data_set,
+        read_third_data_set,
+        read_fourth_data_set,
+    ] {
+        assert_eq_unordered_sort!(vec![system], systems_for_set(graph, set));
+    }
+}
+
+#[test]
+fn fallible_systems_create_autosets() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (fallible_writer, fallible_with_custom_error).each_in_auto_sets(),
+    );
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let write_some_set = find_set(graph, "Writes(\"SomeEvent\")");
+    let read_something_set = find_set(graph, "Reads(\"Something\")");
+    let writer_system = find_system(graph, &fallible_writer);
+    let custom_error_system = find_system(graph, &fallible_with_custom_error);
+
+    assert_eq_unordered_sort!(vec![writer_system], systems_for_set(graph, write_some_set));
+    assert_eq_unordered_sort!(
+        vec![custom_error_system],
+        systems_for_set(graph, read_something_set)
+    );
+}
+
 #[test]
 fn sanity_each() {
     (resource_only,).each_in_auto_sets();
@@ -261,6 +403,155 @@ fn find_set_pair(graph: &ScheduleGraph, type_name: &str) -> (NodeId, NodeId) {
     )
 }
 
+fn find_modify_pair(graph: &ScheduleGraph, type_name: &str) -> (NodeId, NodeId) {
+    (
+        find_set(graph, &format!("Reads(\"{type_name}\")")),
+        find_set(graph, &format!("Modifies(\"{type_name}\")")),
+    )
+}
+
+#[test]
+fn input_systems_lend_autosets() {
+    let mut app = App::new();
+    let (consume, consume_count) = resource_only.in_auto_sets_of(consume_count);
+    app.add_systems(
+        Update,
+        (
+            consume,
+            other_res_only.in_auto_sets_of(inspect_count).0,
+            exclusive_system.in_auto_sets_of(increment_count).0,
+        ),
+    );
+    app.insert_resource(Something);
+    let count_id = app.world_mut().register_system(consume_count);
+    app.world_mut().run_system_with(count_id, 3).unwrap();
+    app.world_mut().run_system_with(count_id, 5).unwrap();
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let read_something_set = find_set(graph, "Reads(\"Something\")");
+    let write_some_set = find_set(graph, "Writes(\"SomeEvent\")");
+    let write_other_data_set = find_set(graph, "Modifies(\"OtherData\")");
+    let consume_system = find_system(graph, &resource_only);
+    let inspect_system = find_system(graph, &other_res_only);
+    let increment_system = find_system(graph, &exclusive_system);
+
+    assert_eq_unordered_sort!(
+        vec![consume_system],
+        systems_for_set(graph, read_something_set)
+    );
+    assert_eq_unordered_sort!(vec![inspect_system], systems_for_set(graph, write_some_set));
+    assert_eq_unordered_sort!(
+        vec![increment_system],
+        systems_for_set(graph, write_other_data_set)
+    );
+}
+
+#[test]
+fn world_systems_create_barrier_autosets() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (exclusive_system, world_reader, fallible_exclusive_system).each_in_auto_sets(),
+    );
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let writes_all_set = find_set(graph, "WritesAll");
+    let reads_all_set = find_set(graph, "ReadsAll");
+    let read_something_set = find_set(graph, "Reads(\"Something\")");
+    let exclusive = find_system(graph, &exclusive_system);
+    let fallible_exclusive = find_system(graph, &fallible_exclusive_system);
+    let reader = find_system(graph, &world_reader);
+
+    assert_eq_unordered_sort!(
+        vec![exclusive, fallible_exclusive],
+        systems_for_set(graph, writes_all_set)
+    );
+    assert_eq_unordered_sort!(vec![reader], systems_for_set(graph, reads_all_set));
+    assert_eq_unordered_sort!(vec![reader], systems_for_set(graph, read_something_set));
+}
+
+#[test]
+fn exclusive_flow_creates_write_sets() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        exclusive_system.exclusive_flow::<(SomeData, Something)>(),
+    );
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let write_some_data_set = find_set(graph, "Writes(\"SomeData\")");
+    let write_something_set = find_set(graph, "Writes(\"Something\")");
+    let system = find_system(graph, &exclusive_system);
+
+    assert_eq!(3, graph.system_sets().count());
+    assert_eq_unordered_sort!(vec![system], systems_for_set(graph, write_some_data_set));
+    assert_eq_unordered_sort!(vec![system], systems_for_set(graph, write_something_set));
+}
+
+#[test]
+fn produced_data_goes_to_write_sets() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (
+            with_query.produces::<OtherData>().in_auto_sets(),
+            resource_mixed.produces::<OtherData>().in_auto_sets(),
+        ),
+    );
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let write_other_data_set = find_set(graph, "Writes(\"OtherData\")");
+    let modify_else_set = find_set(graph, "Modifies(\"SomethingElse\")");
+    let query_system = find_system(graph, &with_query);
+    let mixed_system = find_system(graph, &resource_mixed);
+
+    assert_eq!(6, graph.system_sets().count());
+    assert!(graph
+        .system_sets()
+        .all(|(_, set, _)| format!("{set:?}") != "Modifies(\"OtherData\")"));
+    assert_eq_unordered_sort!(
+        vec![query_system],
+        systems_for_set(graph, write_other_data_set)
+    );
+    assert_eq_unordered_sort!(vec![mixed_system], systems_for_set(graph, modify_else_set));
+}
+
+#[test]
+fn excepted_types_get_no_autosets() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (
+            resource_mixed.in_auto_sets_except::<(Something,)>(),
+            with_query.in_auto_sets_except::<(SomeData, OtherData)>(),
+        ),
+    );
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let write_else_set = find_set(graph, "Modifies(\"SomethingElse\")");
+    let mixed_system = find_system(graph, &resource_mixed);
+
+    assert_eq!(3, graph.system_sets().count());
+    assert_eq_unordered_sort!(vec![mixed_system], systems_for_set(graph, write_else_set));
+}
+
+#[test]
+fn excepted_types_keep_declared_sets() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        record_ignored(&[TypeId::of::<Something>()], || {
+            resource_mixed.reads::<Something>()
+        }),
+    );
+
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let read_set = find_set(graph, "Reads(\"Something\")");
+    let mixed_system = find_system(graph, &resource_mixed);
+
+    assert_eq_unordered_sort!(vec![mixed_system], systems_for_set(graph, read_set));
+}
+
 fn find_system<I: SystemInput, O, M, T: IntoSystem<I, O, M>>(
     graph: &ScheduleGraph,
     _: &T,
@@ -273,6 +564,15 @@ fn find_system<I: SystemInput, O, M, T: IntoSystem<I, O, M>>(
         .unwrap()
 }
 
+fn find_system_by_name(graph: &ScheduleGraph, prefix: &str) -> NodeId {
+    let mut candidates = graph
+        .systems()
+        .filter(|s| s.1.name().starts_with(prefix))
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|s| s.1.name().len());
+    candidates.first().map(|s| s.0).unwrap()
+}
+
 fn systems_for_set(graph: &ScheduleGraph, set: NodeId) -> Vec<NodeId> {
     graph.hierarchy().graph().neighbors(set).collect()
 }
@@ -321,6 +621,45 @@ struct DataNumberThree;
 #[derive(Component)]
 struct DataNumberFour;
 
+#[derive(SystemParam, AutoSetArg)]
+struct CustomParam<'w, 's> {
+    _reader: EventReader<'w, 's, SomeEvent>,
+    _resource: ResMut<'w, Something>,
+    _nested: NestedParam<'w, 's>,
+}
+
+#[derive(SystemParam, AutoSetArg)]
+struct NestedParam<'w, 's> {
+    _query: Query<'w, 's, (&'static SomeData, &'static mut OtherData)>,
+    _commands: Commands<'w, 's>,
+}
+
+#[derive(QueryData, AutoSetArgInQuery)]
+#[query_data(mutable)]
+struct CustomQuery {
+    some: &'static mut SomeData,
+    other: &'static OtherData,
+    nested: NestedQuery,
+}
+
+#[derive(QueryData, AutoSetArgInQuery)]
+struct NestedQuery {
+    entity: Entity,
+    third: Option<&'static DataNumberThree>,
+}
+
+#[derive(QueryFilter, AutoSetArgInQueryFilter)]
+struct CustomFilter {
+    with: With<OtherData>,
+    nested: NestedFilter,
+}
+
+#[derive(QueryFilter, AutoSetArgInQueryFilter)]
+struct NestedFilter {
+    without: Without<DataNumberThree>,
+    changed: Or<(Changed<DataNumberFour>, Added<DataNumberFour>)>,
+}
+
 fn some_reader_only(_reader: EventReader<SomeEvent>) {}
 
 fn some_writer_only(_writer: EventWriter<SomeEvent>) {}
@@ -347,6 +686,7 @@ fn resource_mixed(_read: Res<Something>, _write: ResMut<SomethingElse>) {}
 
 fn with_query(_q: Query<(&SomeData, &mut OtherData, Entity)>) {}
 
+#[allow(clippy::type_complexity)]
 fn with_query_filter(
     _q: Query<
         &SomeData,
@@ -358,6 +698,7 @@ fn with_query_filter(
 ) {
 }
 
+#[allow(clippy::type_complexity)]
 fn big_system(
     _ps: ParamSet<(
         ParallelCommands,
@@ -367,3 +708,57 @@ fn big_system(
     )>,
 ) {
 }
+
+fn produce_count(_writer: EventWriter<SomeEvent>) -> u32 {
+    0
+}
+
+fn forward_count(In(count): In<u32>, _query: Query<&mut OtherData>) -> u32 {
+    count
+}
+
+fn consume_count(In(_count): In<u32>, _resource: Res<Something>) {}
+
+fn inspect_count(InRef(_count): InRef<u32>, _writer: EventWriter<SomeEvent>) {}
+
+fn increment_count(InMut(count): InMut<u32>, _query: Query<&mut OtherData>) {
+    *count += 1;
+}
+
+fn exclusive_system(_world: &mut World) {}
+
+#[allow(clippy::unnecessary_wraps)]
+fn fallible_exclusive_system(_world: &mut World) -> Result {
+    Ok(())
+}
+
+fn world_reader(_world: &World, _resource: Res<Something>) {}
+
+fn with_custom_param(_param: CustomParam) {}
+
+fn with_custom_query(_q: Query<CustomQuery>) {}
+
+fn with_read_only_custom_query(_q: Query<CustomQueryReadOnly>) {}
+
+fn with_custom_filter(_q: Query<&SomeData, CustomFilter>) {}
+
+#[allow(clippy::unnecessary_wraps)]
+fn fallible_writer(_writer: EventWriter<SomeEvent>) -> Result {
+    Ok(())
+}
+
+#[derive(Debug)]
+struct CustomError;
+
+impl std::fmt::Display for CustomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "custom error")
+    }
+}
+
+impl std::error::Error for CustomError {}
+
+#[allow(clippy::unnecessary_wraps)]
+fn fallible_with_custom_error(_resource: Res<Something>) -> Result<(), CustomError> {
+    Ok(())
+}
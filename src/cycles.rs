@@ -0,0 +1,315 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    fmt::{self, Display, Formatter, Write},
+};
+
+use bevy::{
+    ecs::schedule::{graph::DiGraph, ScheduleBuildError, ScheduleBuildPass, ScheduleGraph},
+    prelude::World,
+};
+use disqualified::ShortName;
+
+use crate::{
+    access::build_only_pass,
+    flow_graph::{target_name, FlowGraph, OrderNode},
+    introspect::FlowSystem,
+    manifest::FlowConstraint,
+    markers::FlowAccess,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Cycle of flow constraints that makes the schedule impossible to build, returned by
+/// [`FlowIntrospection::flow_cycles`][crate::introspect::FlowIntrospection::flow_cycles] and
+/// reported by [`FlowCyclePass`].
+///
+/// Its [`Display`] implementation explains the cycle in terms of data flow:
+///
+/// ```text
+/// `physics` writes `Velocity` and reads `Collision`; `collide` writes `Collision` and reads
+/// `Velocity`; constraints `Modifies("Velocity") before Reads("Velocity")` and
+/// `Modifies("Collision") before Reads("Collision")` form a cycle. Consider dropping
+/// `Modifies("Collision") before Reads("Collision")`.
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FlowCycle {
+    /// Systems on the cycle, in the order they would have to run.
+    pub systems: Vec<CycleSystem>,
+    /// Constraints forming the cycle, in the same order.
+    pub constraints: Vec<FlowConstraint>,
+    /// Constraint that is best dropped to break the cycle: the one taking part in the most cycles
+    /// of the schedule.
+    pub suggested_removal: FlowConstraint,
+}
+
+/// System on a [`FlowCycle`] with the flow linking it to its neighbours on the cycle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CycleSystem {
+    /// The system.
+    pub system: FlowSystem,
+    /// Types read by the system that the cycle passes through, `*` standing for all data.
+    pub reads: Vec<String>,
+    /// Types written by the system that the cycle passes through, `*` standing for all data.
+    pub writes: Vec<String>,
+}
+
+/// Schedule build pass failing the build with a data-flow explanation of cycles formed by flow
+/// constraints, instead of the one Bevy gives in terms of sets and systems.
+///
+/// The error is still a [`ScheduleBuildError::DependencyCycle`], listing each [`FlowCycle`] on its
+/// own line. Cycles between the sets themselves, like
+/// [`write_before_read::<A>()`][crate::ordering::write_before_read] together with
+/// [`read_before_write::<A>()`][crate::ordering::read_before_write], are reported by Bevy before
+/// build passes run, so they keep the usual error; use
+/// [`.flow_cycles()`][crate::introspect::FlowIntrospection::flow_cycles] to explain those.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # let mut app = App::new();
+/// app.edit_schedule(Update, |schedule| {
+///     schedule.add_build_pass(FlowCyclePass);
+/// });
+/// ```
+#[derive(Debug, Default)]
+pub struct FlowCyclePass;
+
+impl ScheduleBuildPass for FlowCyclePass {
+    build_only_pass!();
+
+    fn build(
+        &mut self,
+        world: &mut World,
+        graph: &mut ScheduleGraph,
+        _dependency_flattened: &mut DiGraph,
+    ) -> Result<(), ScheduleBuildError> {
+        let cycles = find_cycles(&FlowGraph::extract_in(world, graph));
+        if cycles.is_empty() {
+            return Ok(());
+        }
+        let mut message = String::new();
+        for cycle in cycles {
+            let _ = writeln!(message, " -- {cycle}");
+        }
+        Err(ScheduleBuildError::DependencyCycle(message))
+    }
+}
+
+/// Finds cycles formed by constraints with a flow set on at least one side. Cycles going through
+/// other constraints, like the ones added by `.chain()`, are not found.
+pub(crate) fn find_cycles(flow: &FlowGraph) -> Vec<FlowCycle> {
+    let mut edges = HashMap::<OrderNode, Vec<OrderNode>>::new();
+    for &(set, _, _) in &flow.sets {
+        edges
+            .entry(OrderNode::Start(set))
+            .or_default()
+            .push(OrderNode::End(set));
+    }
+    for &(system, set, _) in &flow.memberships {
+        edges
+            .entry(OrderNode::Start(set))
+            .or_default()
+            .push(OrderNode::System(system));
+        edges
+            .entry(OrderNode::System(system))
+            .or_default()
+            .push(OrderNode::End(set));
+    }
+    let constraints = flow
+        .constraints
+        .iter()
+        .map(|&(before, after)| (OrderNode::exit(before), OrderNode::enter(after)))
+        .collect::<Vec<_>>();
+    for &(from, to) in &constraints {
+        edges.entry(from).or_default().push(to);
+    }
+
+    let mut found = Vec::<Vec<OrderNode>>::new();
+    let mut known = Vec::<Vec<usize>>::new();
+    for &(from, to) in &constraints {
+        let Some(mut path) = shortest_path(&edges, to, from) else {
+            continue;
+        };
+        path.insert(0, from);
+        path.pop();
+        let mut used = constraint_indices(&constraints, &path);
+        used.sort_unstable();
+        if !known.contains(&used) {
+            known.push(used);
+            found.push(path);
+        }
+    }
+
+    let mut counts = vec![0_usize; constraints.len()];
+    for index in known.iter().flatten() {
+        counts[*index] += 1;
+    }
+
+    let describe = |index: usize| {
+        let (before, after) = flow.constraints[index];
+        FlowConstraint {
+            before: flow.node_name(before),
+            after: flow.node_name(after),
+        }
+    };
+
+    found
+        .iter()
+        .map(|path| {
+            let used = constraint_indices(&constraints, path);
+            let removed = used
+                .iter()
+                .copied()
+                .max_by_key(|&index| (counts[index], index))
+                .unwrap_or_default();
+            FlowCycle {
+                systems: cycle_systems(flow, path),
+                constraints: used.into_iter().map(describe).collect(),
+                suggested_removal: describe(removed),
+            }
+        })
+        .collect()
+}
+
+/// Returns the systems on the cyclic `path` with the flow sets it enters them through and leaves
+/// them through.
+fn cycle_systems(flow: &FlowGraph, path: &[OrderNode]) -> Vec<CycleSystem> {
+    let mut systems = Vec::new();
+    for (position, node) in path.iter().enumerate() {
+        let OrderNode::System(id) = *node else {
+            continue;
+        };
+        let mut system = CycleSystem {
+            system: FlowSystem {
+                id,
+                name: flow.node_name(id),
+            },
+            reads: Vec::new(),
+            writes: Vec::new(),
+        };
+        // Constraints may also lead straight to a set the system doesn't belong to.
+        let previous = path[(position + path.len() - 1) % path.len()];
+        let next = path[(position + 1) % path.len()];
+        let sets = [previous, next]
+            .into_iter()
+            .filter_map(|neighbour| match neighbour {
+                OrderNode::Start(set) if neighbour == previous => Some(set),
+                OrderNode::End(set) if neighbour == next => Some(set),
+                _ => None,
+            });
+        for set in sets {
+            let Some((_, kind, _)) = flow.set(set) else {
+                continue;
+            };
+            let list = match kind.access() {
+                FlowAccess::Read => &mut system.reads,
+                FlowAccess::Write | FlowAccess::Modify => &mut system.writes,
+            };
+            let name = target_name(kind);
+            if !list.contains(&name) {
+                list.push(name);
+            }
+        }
+        systems.push(system);
+    }
+    systems
+}
+
+/// Returns the nodes on the shortest path from `from` to `to`, including both ends.
+fn shortest_path(
+    edges: &HashMap<OrderNode, Vec<OrderNode>>,
+    from: OrderNode,
+    to: OrderNode,
+) -> Option<Vec<OrderNode>> {
+    let mut previous = HashMap::from([(from, from)]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(node) = queue.pop_front() {
+        if node == to {
+            let mut path = vec![to];
+            let mut current = to;
+            while current != from {
+                current = previous[&current];
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for &next in edges.get(&node).into_iter().flatten() {
+            if let Entry::Vacant(entry) = previous.entry(next) {
+                entry.insert(node);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Returns indices of `constraints` connecting consecutive nodes of the cyclic `path`.
+fn constraint_indices(constraints: &[(OrderNode, OrderNode)], path: &[OrderNode]) -> Vec<usize> {
+    (0..path.len())
+        .filter_map(|position| {
+            let edge = (path[position], path[(position + 1) % path.len()]);
+            constraints
+                .iter()
+                .position(|&constraint| constraint == edge)
+        })
+        .collect()
+}
+
+impl Display for FlowCycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for system in &self.systems {
+            write!(f, "`{}`", ShortName(&system.system.name))?;
+            let writes = short_names(&system.writes);
+            let reads = short_names(&system.reads);
+            let flow = match (writes.is_empty(), reads.is_empty()) {
+                (false, false) => format!(" writes {} and reads {}", list(&writes), list(&reads)),
+                (false, true) => format!(" writes {}", list(&writes)),
+                (true, false) => format!(" reads {}", list(&reads)),
+                (true, true) => " is constrained directly".to_owned(),
+            };
+            f.write_str(&flow)?;
+            write!(f, "; ")?;
+        }
+        let constraints = self
+            .constraints
+            .iter()
+            .map(constraint_name)
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "constraints {} form a cycle. Consider dropping `{}`.",
+            list(&constraints),
+            constraint_name(&self.suggested_removal)
+        )
+    }
+}
+
+fn short_names(names: &[String]) -> Vec<String> {
+    names
+        .iter()
+        .map(|name| ShortName(name).to_string())
+        .collect()
+}
+
+fn constraint_name(constraint: &FlowConstraint) -> String {
+    format!(
+        "{} before {}",
+        ShortName(&constraint.before),
+        ShortName(&constraint.after)
+    )
+}
+
+/// Joins `items` into a list like "`a`, `b` and `c`".
+fn list(items: &[String]) -> String {
+    let items = items
+        .iter()
+        .map(|item| format!("`{item}`"))
+        .collect::<Vec<_>>();
+    match items.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {last}", rest.join(", ")),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::{
+    ecs::schedule::{ScheduleBuildError, Schedules},
+    prelude::*,
+};
+
+use super::FlowCyclePass;
+use crate::prelude::{read_before_write, write_before_read, FlowIntrospection, InferFlow};
+
+#[test]
+fn conflicting_set_constraints_form_a_cycle() {
+    let mut app = App::new();
+    app.configure_sets(
+        Update,
+        (
+            write_before_read::<Velocity>(),
+            read_before_write::<Velocity>(),
+        ),
+    );
+    assert_build_fails(&mut app);
+
+    let cycles = app.get_schedule(Update).unwrap().flow_cycles();
+    assert_eq!(cycles.len(), 2);
+    for cycle in &cycles {
+        assert!(cycle.systems.is_empty());
+        assert_eq!(cycle.constraints.len(), 2);
+        let explanation = cycle.to_string();
+        assert!(explanation.starts_with("constraints `"));
+        assert!(explanation.contains("form a cycle. Consider dropping `"));
+    }
+    let explanations = cycles.iter().map(ToString::to_string).collect::<String>();
+    for set in ["Writes(\"Velocity\")", "Modifies(\"Velocity\")"] {
+        assert!(explanations.contains(&format!("`{set} before Reads(\"Velocity\")`")));
+        assert!(explanations.contains(&format!("`Reads(\"Velocity\") before {set}`")));
+    }
+}
+
+#[test]
+fn cycle_through_systems_is_explained() {
+    let mut app = App::new();
+    app.add_systems(Update, (physics.in_auto_sets(), collide.in_auto_sets()))
+        .configure_sets(
+            Update,
+            (
+                write_before_read::<Velocity>(),
+                write_before_read::<Collision>(),
+            ),
+        );
+    assert_build_fails(&mut app);
+
+    let cycles = app.get_schedule(Update).unwrap().flow_cycles();
+    assert_eq!(cycles.len(), 1);
+    let cycle = &cycles[0];
+    assert_eq!(cycle.systems.len(), 2);
+    assert_eq!(cycle.constraints.len(), 2);
+    assert_eq!(cycle.suggested_removal.before, "Modifies(\"Velocity\")");
+
+    let explanation = cycle.to_string();
+    assert!(explanation.contains("`physics` writes `Velocity` and reads `Collision`"));
+    assert!(explanation.contains("`collide` writes `Collision` and reads `Velocity`"));
+    assert!(explanation.ends_with(
+        "form a cycle. Consider dropping `Modifies(\"Velocity\") before Reads(\"Velocity\")`."
+    ));
+}
+
+#[test]
+fn pass_explains_cycle_through_systems() {
+    let mut app = App::new();
+    app.add_systems(Update, (physics.in_auto_sets(), collide.in_auto_sets()))
+        .configure_sets(
+            Update,
+            (
+                write_before_read::<Velocity>(),
+                write_before_read::<Collision>(),
+            ),
+        )
+        .edit_schedule(Update, |schedule| {
+            schedule.add_build_pass(FlowCyclePass);
+        });
+
+    let mut schedule = app
+        .world_mut()
+        .resource_mut::<Schedules>()
+        .remove(Update)
+        .unwrap();
+    let error = schedule.initialize(app.world_mut()).unwrap_err();
+    let ScheduleBuildError::DependencyCycle(message) = error else {
+        panic!("expected a dependency cycle, got {error:?}");
+    };
+    assert!(message.contains("`physics` writes `Velocity` and reads `Collision`"));
+    assert!(
+        message.contains("Consider dropping `Modifies(\"Velocity\") before Reads(\"Velocity\")`.")
+    );
+}
+
+#[test]
+fn excepted_flow_breaks_the_cycle() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (
+            physics.in_auto_sets(),
+            collide.in_auto_sets_except::<(Velocity,)>(),
+        ),
+    )
+    .configure_sets(
+        Update,
+        (
+            write_before_read::<Velocity>(),
+            write_before_read::<Collision>(),
+        ),
+    );
+
+    assert!(app.get_schedule(Update).unwrap().flow_cycles().is_empty());
+}
+
+#[test]
+fn valid_schedule_has_no_cycles() {
+    let mut app = App::new();
+    app.add_systems(Update, (physics.in_auto_sets(), collide.in_auto_sets()))
+        .configure_sets(Update, write_before_read::<Velocity>());
+
+    assert!(app.get_schedule(Update).unwrap().flow_cycles().is_empty());
+}
+
+fn assert_build_fails(app: &mut App) {
+    let mut schedule = app
+        .world_mut()
+        .resource_mut::<Schedules>()
+        .remove(Update)
+        .unwrap();
+    assert!(schedule.initialize(app.world_mut()).is_err());
+    app.add_schedule(schedule);
+}
+
+#[derive(Resource)]
+struct Velocity;
+
+#[derive(Resource)]
+struct Collision;
+
+fn physics(_velocity: ResMut<Velocity>, _collision: Res<Collision>) {}
+
+fn collide(_collision: ResMut<Collision>, _velocity: Res<Velocity>) {}
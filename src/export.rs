@@ -0,0 +1,145 @@
+use std::fmt::Write;
+
+use bevy::ecs::schedule::{NodeId, Schedule};
+
+use crate::{flow_graph::FlowGraph, markers::FlowAccess};
+
+#[cfg(test)]
+mod tests;
+
+/// Renders the data flow of `schedule` as a Graphviz DOT document.
+///
+/// Systems are drawn as boxes and auto-sets as ellipses. A system writing to a set points to it,
+/// and a set points to the systems reading from it. Dashed edges show the constraints, like the
+/// ones added by [`write_before_read`][crate::ordering::write_before_read].
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # let mut app = App::new();
+/// # app.add_systems(Update, || {});
+/// let dot = flow_to_dot(app.get_schedule(Update).unwrap());
+/// ```
+#[must_use]
+pub fn flow_to_dot(schedule: &Schedule) -> String {
+    let flow = FlowGraph::from_schedule(schedule);
+    let mut out = String::new();
+
+    let title = format!("{:?}", schedule.label());
+    let _ = writeln!(out, "digraph \"{}\" {{", escape_dot(&title));
+    let _ = writeln!(out, "    rankdir=LR;");
+    for (id, name) in &flow.systems {
+        let _ = writeln!(
+            out,
+            "    {} [label=\"{}\", shape=box];",
+            node_name(*id),
+            escape_dot(name)
+        );
+    }
+    for (id, _, name) in &flow.sets {
+        let _ = writeln!(
+            out,
+            "    {} [label=\"{}\", shape=ellipse];",
+            node_name(*id),
+            escape_dot(name)
+        );
+    }
+    for &(system, set, _) in &flow.memberships {
+        let Some((_, kind, _)) = flow.set(set) else {
+            continue;
+        };
+        let (from, to, label) = match kind.access() {
+            FlowAccess::Read => (set, system, "reads"),
+            FlowAccess::Write => (system, set, "writes"),
+            FlowAccess::Modify => (system, set, "modifies"),
+        };
+        let _ = writeln!(
+            out,
+            "    {} -> {} [label=\"{label}\"];",
+            node_name(from),
+            node_name(to)
+        );
+    }
+    for &(before, after) in &flow.constraints {
+        let _ = writeln!(
+            out,
+            "    {} -> {} [label=\"before\", style=dashed];",
+            node_name(before),
+            node_name(after)
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Renders the data flow of `schedule` as a Mermaid flowchart.
+///
+/// The layout is the same as in [`flow_to_dot`]: systems are rectangles, auto-sets are stadiums
+/// and constraints are dotted edges.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # let mut app = App::new();
+/// # app.add_systems(Update, || {});
+/// let mermaid = flow_to_mermaid(app.get_schedule(Update).unwrap());
+/// ```
+#[must_use]
+pub fn flow_to_mermaid(schedule: &Schedule) -> String {
+    let flow = FlowGraph::from_schedule(schedule);
+    let mut out = String::from("flowchart LR\n");
+
+    for (id, name) in &flow.systems {
+        let _ = writeln!(out, "    {}[\"{}\"]", node_name(*id), escape_mermaid(name));
+    }
+    for (id, _, name) in &flow.sets {
+        let _ = writeln!(
+            out,
+            "    {}([\"{}\"])",
+            node_name(*id),
+            escape_mermaid(name)
+        );
+    }
+    for &(system, set, _) in &flow.memberships {
+        let Some((_, kind, _)) = flow.set(set) else {
+            continue;
+        };
+        let (from, to, label) = match kind.access() {
+            FlowAccess::Read => (set, system, "reads"),
+            FlowAccess::Write => (system, set, "writes"),
+            FlowAccess::Modify => (system, set, "modifies"),
+        };
+        let _ = writeln!(
+            out,
+            "    {} -->|{label}| {}",
+            node_name(from),
+            node_name(to)
+        );
+    }
+    for &(before, after) in &flow.constraints {
+        let _ = writeln!(
+            out,
+            "    {} -.->|before| {}",
+            node_name(before),
+            node_name(after)
+        );
+    }
+    out
+}
+
+fn node_name(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system{index}"),
+        NodeId::Set(index) => format!("set{index}"),
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
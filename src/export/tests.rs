@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+
+use super::{flow_to_dot, flow_to_mermaid};
+use crate::prelude::{write_before_read, InferFlowEach};
+
+#[test]
+fn dot_shows_systems_sets_and_constraints() {
+    let mut app = App::new();
+    app.add_systems(Update, (score_writer, score_reader).each_in_auto_sets())
+        .configure_sets(Update, write_before_read::<Score>());
+
+    let dot = flow_to_dot(app.get_schedule(Update).unwrap());
+    let (writer, reader) = system_names(&app);
+    let (read_set, write_set) = set_names(&app);
+
+    assert!(dot.starts_with("digraph \"Update\" {\n"));
+    assert!(dot.contains(&format!(
+        "{writer} [label=\"{}\", shape=box];",
+        any_name(&score_writer)
+    )));
+    assert!(dot.contains(&format!(
+        "{write_set} [label=\"Modifies(\\\"Score\\\")\", shape=ellipse];"
+    )));
+    assert!(dot.contains(&format!("{writer} -> {write_set} [label=\"modifies\"];")));
+    assert!(dot.contains(&format!("{read_set} -> {reader} [label=\"reads\"];")));
+    assert!(dot.contains(&format!(
+        "{write_set} -> {read_set} [label=\"before\", style=dashed];"
+    )));
+    assert!(dot.ends_with("}\n"));
+}
+
+#[test]
+fn mermaid_shows_systems_sets_and_constraints() {
+    let mut app = App::new();
+    app.add_systems(Update, (score_writer, score_reader).each_in_auto_sets())
+        .configure_sets(Update, write_before_read::<Score>());
+
+    let mermaid = flow_to_mermaid(app.get_schedule(Update).unwrap());
+    let (writer, reader) = system_names(&app);
+    let (read_set, write_set) = set_names(&app);
+
+    assert!(mermaid.starts_with("flowchart LR\n"));
+    assert!(mermaid.contains(&format!("{write_set}([\"Modifies(#quot;Score#quot;)\"])")));
+    assert!(mermaid.contains(&format!("{writer} -->|modifies| {write_set}")));
+    assert!(mermaid.contains(&format!("{read_set} -->|reads| {reader}")));
+    assert!(mermaid.contains(&format!("{write_set} -.->|before| {read_set}")));
+}
+
+fn system_names(app: &App) -> (String, String) {
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let find = |name: &str| {
+        graph
+            .systems()
+            .find(|(_, system, _)| system.name() == name)
+            .map(|(id, _, _)| format!("system{}", id.index()))
+            .unwrap()
+    };
+    (find(any_name(&score_writer)), find(any_name(&score_reader)))
+}
+
+fn set_names(app: &App) -> (String, String) {
+    let graph = app.get_schedule(Update).unwrap().graph();
+    let find = |name: &str| {
+        graph
+            .system_sets()
+            .find(|(_, set, _)| format!("{set:?}") == name)
+            .map(|(id, _, _)| format!("set{}", id.index()))
+            .unwrap()
+    };
+    (find("Reads(\"Score\")"), find("Modifies(\"Score\")"))
+}
+
+fn any_name<T>(_: &T) -> &'static str {
+    std::any::type_name::<T>()
+}
+
+#[derive(Resource)]
+struct Score;
+
+fn score_writer(_score: ResMut<Score>) {}
+
+fn score_reader(_score: Res<Score>) {}
@@ -0,0 +1,405 @@
+use std::{
+    any::TypeId,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    iter,
+    sync::{LazyLock, Mutex},
+};
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, Components},
+        schedule::{
+            graph::GraphInfo, Chain, InternedSystemSet, NodeId, Schedulable, Schedule,
+            ScheduleConfigs, ScheduleGraph,
+        },
+        system::{ScheduleSystem, System},
+    },
+    prelude::World,
+};
+
+use crate::{
+    access::{access_flows, access_inferred, recorded_access_flows, sets_in_set, systems_in_set},
+    markers::{AnyFlowSet, EventFlow, FlowTarget},
+};
+
+thread_local! {
+    static RECORDING: RefCell<Option<Vec<InternedSystemSet>>> = const { RefCell::new(None) };
+    static PRODUCED: RefCell<Vec<TypeId>> = const { RefCell::new(Vec::new()) };
+    static IGNORED: RefCell<Vec<TypeId>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Flow sets that systems were put in by inference, keyed by the type of the system, so
+/// memberships can be told apart from ones declared manually. A system added more than once is
+/// treated the same way everywhere, since the type identifies it.
+static INFERRED: LazyLock<Mutex<HashMap<TypeId, HashSet<AnyFlowSet>>>> =
+    LazyLock::new(Mutex::default);
+
+/// Runs `infer`, recording the flow sets added by it as inferred for systems of type `system`.
+pub(crate) fn record_inferred<S>(
+    system: TypeId,
+    infer: impl FnOnce() -> ScheduleConfigs<S>,
+) -> ScheduleConfigs<S>
+where
+    S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+{
+    let previous = RECORDING.with(|recording| recording.replace(Some(Vec::new())));
+    let configs = infer();
+    let sets = RECORDING
+        .with(|recording| recording.replace(previous))
+        .unwrap_or_default();
+    INFERRED
+        .lock()
+        .unwrap()
+        .entry(system)
+        .or_default()
+        .extend(sets.iter().filter_map(|set| AnyFlowSet::from_set(&**set)));
+    configs
+}
+
+/// Notes that `set` was added to a system, so it can be recognized as inferred.
+pub(crate) fn note_flow_set(set: InternedSystemSet) {
+    RECORDING.with(|recording| {
+        if let Some(sets) = recording.borrow_mut().as_mut() {
+            sets.push(set);
+        }
+    });
+}
+
+/// Runs `infer`, so mutable access to `produced` is inferred as [`Writes`] instead of
+/// [`Modifies`].
+///
+/// [`Writes`]: crate::markers::Writes
+/// [`Modifies`]: crate::markers::Modifies
+pub(crate) fn record_produced<R>(produced: TypeId, infer: impl FnOnce() -> R) -> R {
+    PRODUCED.with(|types| types.borrow_mut().push(produced));
+    let result = infer();
+    PRODUCED.with(|types| types.borrow_mut().pop());
+    result
+}
+
+/// Returns `true` if mutable access to `target` is inferred as [`Writes`][crate::markers::Writes].
+pub(crate) fn is_produced(target: TypeId) -> bool {
+    PRODUCED.with(|types| types.borrow().contains(&target))
+}
+
+/// Runs `infer`, so no flow sets are added for `ignored` types.
+pub(crate) fn record_ignored<R>(ignored: &[TypeId], infer: impl FnOnce() -> R) -> R {
+    let previous = IGNORED.with(|types| {
+        let mut types = types.borrow_mut();
+        let previous = types.len();
+        types.extend_from_slice(ignored);
+        previous
+    });
+    let result = infer();
+    IGNORED.with(|types| types.borrow_mut().truncate(previous));
+    result
+}
+
+/// Returns `true` if flow sets of `target` are left out during inference.
+pub(crate) fn is_ignored(target: TypeId) -> bool {
+    IGNORED.with(|types| types.borrow().contains(&target))
+}
+
+/// Returns targets of events used in `graph`, keyed by the id of their `Events<E>` resource.
+/// Events are recognized by [`EventFlow`] sets, and only ones with the resource registered in
+/// `components` are returned.
+pub(crate) fn event_resources(
+    graph: &ScheduleGraph,
+    components: &Components,
+) -> HashMap<ComponentId, FlowTarget> {
+    graph
+        .system_sets()
+        .filter_map(|(_, set, _)| set.as_dyn_eq().as_any().downcast_ref::<EventFlow>())
+        .filter_map(|event| {
+            let resource = components.get_resource_id(event.resource())?;
+            Some((resource, event.target()))
+        })
+        .collect()
+}
+
+/// Node of an ordering graph. Each set is split into its start and end, so an ordering constraint
+/// can enter a set before its members and leave it after them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum OrderNode {
+    System(NodeId),
+    Start(NodeId),
+    End(NodeId),
+}
+
+impl OrderNode {
+    /// Returns the node that constraints on `id` start from.
+    pub(crate) fn exit(id: NodeId) -> Self {
+        if id.is_system() {
+            Self::System(id)
+        } else {
+            Self::End(id)
+        }
+    }
+
+    /// Returns the node that constraints on `id` lead to.
+    pub(crate) fn enter(id: NodeId) -> Self {
+        if id.is_system() {
+            Self::System(id)
+        } else {
+            Self::Start(id)
+        }
+    }
+}
+
+/// Returns edges of the ordering graph of all systems and sets in `graph`, so a system is
+/// reachable from another one if and only if it is guaranteed to run after it. Systems are treated
+/// as members of the flow sets they belong to according to their access in `flow`.
+pub(crate) fn ordering_graph(
+    graph: &ScheduleGraph,
+    flow: &FlowGraph,
+) -> HashMap<OrderNode, Vec<OrderNode>> {
+    let mut edges = HashMap::<OrderNode, Vec<OrderNode>>::new();
+    for (set, _, _) in graph.system_sets() {
+        edges
+            .entry(OrderNode::Start(set))
+            .or_default()
+            .push(OrderNode::End(set));
+    }
+    let memberships = graph
+        .hierarchy()
+        .graph()
+        .all_edges()
+        .chain(flow.accessed.iter().map(|&(system, set)| (set, system)));
+    for (parent, child) in memberships {
+        edges
+            .entry(OrderNode::Start(parent))
+            .or_default()
+            .push(OrderNode::enter(child));
+        edges
+            .entry(OrderNode::exit(child))
+            .or_default()
+            .push(OrderNode::End(parent));
+    }
+    for (before, after) in graph.dependency().graph().all_edges() {
+        edges
+            .entry(OrderNode::exit(before))
+            .or_default()
+            .push(OrderNode::enter(after));
+    }
+    edges
+}
+
+/// Returns all nodes reachable from `node` in the ordering graph.
+pub(crate) fn reachable(
+    edges: &HashMap<OrderNode, Vec<OrderNode>>,
+    node: OrderNode,
+) -> HashSet<OrderNode> {
+    let mut visited = HashSet::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        for &next in edges.get(&node).into_iter().flatten() {
+            if visited.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    visited
+}
+
+/// Data flow of a schedule, extracted from its [`ScheduleGraph`].
+pub(crate) struct FlowGraph {
+    /// All systems of the schedule with their names.
+    pub(crate) systems: Vec<(NodeId, String)>,
+    /// All flow sets of the schedule with their names.
+    pub(crate) sets: Vec<(NodeId, AnyFlowSet, String)>,
+    /// Pairs of a system and a flow set it belongs to, with a flag telling if the membership was
+    /// inferred, either from the signature or from the access of the system.
+    pub(crate) memberships: Vec<(NodeId, NodeId, bool)>,
+    /// Pairs of a system and a flow set it belongs to according to its access, without the sets
+    /// that constraints nest the flow set in.
+    pub(crate) accessed: Vec<(NodeId, NodeId)>,
+    /// Ordering edges with a flow set on at least one side.
+    pub(crate) constraints: Vec<(NodeId, NodeId)>,
+}
+
+impl FlowGraph {
+    /// Extracts the flow of `graph`. Auto-sets inferred from access can't be matched with flow
+    /// sets without the world, so they are left out.
+    pub(crate) fn extract(graph: &ScheduleGraph) -> Self {
+        let systems = graph
+            .systems()
+            .map(|(id, system, _)| (id, system))
+            .collect::<Vec<_>>();
+        Self::new(graph, &systems, &HashMap::new())
+    }
+
+    /// Extracts the flow of `graph` while its schedule is built, including auto-sets inferred from
+    /// access.
+    pub(crate) fn extract_in(world: &World, graph: &ScheduleGraph) -> Self {
+        let systems = graph
+            .systems()
+            .map(|(id, system, _)| (id, system))
+            .collect::<Vec<_>>();
+        Self::new(graph, &systems, &access_flows(world, graph))
+    }
+
+    /// Extracts the flow of `schedule`, including auto-sets inferred from access by its last
+    /// build.
+    pub(crate) fn from_schedule(schedule: &Schedule) -> Self {
+        let graph = schedule.graph();
+        // Building the schedule moves systems out of the graph, so the built ones are taken from
+        // the schedule, and the ones added since then from the graph.
+        let systems = schedule
+            .systems()
+            .into_iter()
+            .flatten()
+            .chain(graph.systems().map(|(id, system, _)| (id, system)))
+            .collect::<Vec<_>>();
+        let is_inferred = access_inferred(graph);
+        let access = systems
+            .iter()
+            .filter(|&&(id, _)| is_inferred(id))
+            .map(|&(id, system)| (id, recorded_access_flows(system)))
+            .collect();
+        Self::new(graph, &systems, &access)
+    }
+
+    fn new(
+        graph: &ScheduleGraph,
+        systems: &[(NodeId, &ScheduleSystem)],
+        access: &HashMap<NodeId, Vec<AnyFlowSet>>,
+    ) -> Self {
+        let names = systems
+            .iter()
+            .map(|(id, system)| (*id, system.name().into_owned()))
+            .collect::<Vec<_>>();
+
+        let sets = graph
+            .system_sets()
+            .filter_map(|(id, set, _)| {
+                AnyFlowSet::from_set(set).map(|flow| (id, flow, format!("{set:?}")))
+            })
+            .collect::<Vec<_>>();
+
+        let ids = sets
+            .iter()
+            .map(|&(id, flow, _)| (flow, id))
+            .collect::<HashMap<_, _>>();
+        let flows = sets
+            .iter()
+            .map(|&(id, flow, _)| (id, flow))
+            .collect::<HashMap<_, _>>();
+
+        // A system is an inferred member of a flow set if it was put in the set, or in a flow set
+        // nested in it, by inference.
+        let recorded = INFERRED.lock().unwrap();
+        let inferred = systems
+            .iter()
+            .filter_map(|&(id, system)| Some((id, recorded.get(&System::type_id(&**system))?)))
+            .collect::<HashMap<_, _>>();
+        let hierarchy = graph.hierarchy().graph();
+        let is_inferred = |system: NodeId, set: NodeId| {
+            let Some(inferred) = inferred.get(&system) else {
+                return false;
+            };
+            iter::once(set)
+                .chain(sets_in_set(graph, set))
+                .filter(|&parent| hierarchy.contains_edge(parent, system))
+                .filter_map(|parent| flows.get(&parent))
+                .any(|flow| inferred.contains(flow))
+        };
+        let mut memberships = sets
+            .iter()
+            .flat_map(|&(set, _, _)| {
+                systems_in_set(graph, set)
+                    .into_iter()
+                    .map(move |system| (system, set, is_inferred(system, set)))
+            })
+            .collect::<Vec<_>>();
+
+        let accessed = systems
+            .iter()
+            .filter_map(|(system, _)| Some((*system, access.get(system)?)))
+            .flat_map(|(system, flows)| {
+                flows
+                    .iter()
+                    .filter_map(|flow| ids.get(flow))
+                    .map(move |&set| (system, set))
+            })
+            .collect::<Vec<_>>();
+        // Constraints nest flow sets like `WritesAll` in other flow sets, so members of the nested
+        // set belong to the outer sets too.
+        let mut outer = HashMap::<NodeId, Vec<NodeId>>::new();
+        for &(set, _, _) in &sets {
+            for nested in sets_in_set(graph, set) {
+                outer.entry(nested).or_default().push(set);
+            }
+        }
+        let mut known = memberships
+            .iter()
+            .map(|&(system, set, _)| (system, set))
+            .collect::<HashSet<_>>();
+        for &(system, set) in &accessed {
+            for &member_of in iter::once(&set).chain(outer.get(&set).into_iter().flatten()) {
+                if known.insert((system, member_of)) {
+                    memberships.push((system, member_of, true));
+                }
+            }
+        }
+
+        let is_flow_set = |id: NodeId| sets.iter().any(|&(set, _, _)| set == id);
+        let is_known = |id: NodeId| id.is_system() || is_flow_set(id);
+        let constraints = graph
+            .dependency()
+            .graph()
+            .all_edges()
+            .filter(|&(from, to)| {
+                (is_flow_set(from) || is_flow_set(to)) && is_known(from) && is_known(to)
+            })
+            .collect();
+
+        Self {
+            systems: names,
+            sets,
+            memberships,
+            accessed,
+            constraints,
+        }
+    }
+
+    /// Returns the systems in the flow set `id`, or `id` itself if it is a system.
+    pub(crate) fn members(&self, id: NodeId) -> Vec<NodeId> {
+        if id.is_system() {
+            return vec![id];
+        }
+        self.memberships
+            .iter()
+            .filter(|&&(_, set, _)| set == id)
+            .map(|&(system, _, _)| system)
+            .collect()
+    }
+
+    pub(crate) fn set(&self, id: NodeId) -> Option<&(NodeId, AnyFlowSet, String)> {
+        self.sets.iter().find(|(set, _, _)| *set == id)
+    }
+
+    /// Returns the name of a system or the label of a flow set.
+    pub(crate) fn node_name(&self, id: NodeId) -> String {
+        self.set(id).map_or_else(
+            || {
+                self.systems
+                    .iter()
+                    .find(|(system, _)| *system == id)
+                    .map(|(_, name)| name.clone())
+                    .unwrap_or_default()
+            },
+            |(_, _, name)| name.clone(),
+        )
+    }
+}
+
+/// Returns the name of the data `set` is created for, with `*` standing for all data.
+pub(crate) fn target_name(set: &AnyFlowSet) -> String {
+    match set {
+        AnyFlowSet::Flow(flow) => flow
+            .name
+            .map_or_else(|| format!("{:?}", flow.target), str::to_owned),
+        AnyFlowSet::All(_) => "*".to_owned(),
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::ecs::schedule::{graph::GraphInfo, Chain, Schedulable, ScheduleConfigs};
+use bevy::ecs::system::{InfallibleSystemWrapper, ScheduleSystem, System};
+use bevy::prelude::{BevyError, IntoScheduleConfigs, IntoSystem};
+
+use crate::{automagic::InferableSystem, flow_graph::record_inferred};
+
+/// Extension trait for systems running systems that take [`In`], [`InRef`] or [`InMut`] input.
+///
+/// [`In`]: bevy::prelude::In
+/// [`InRef`]: bevy::prelude::InRef
+/// [`InMut`]: bevy::prelude::InMut
+pub trait InferFlowOf<S, Marker>
+where
+    S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+{
+    /// Infers auto-sets for the system like
+    /// [`.in_auto_sets()`][crate::automagic::InferFlow::in_auto_sets], and puts it in the ones
+    /// inferred from the signature of `system` too, which may take input. `system` itself is
+    /// handed back untouched, so it can still be registered with `register_system` and run with
+    /// different inputs through its `SystemId`, or be the receiving end of a pipe.
+    ///
+    /// Use it for the scheduled system that runs `system`, so constraints order it as if it
+    /// accessed the same data:
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_sorting::prelude::*;
+    /// # #[derive(Resource)]
+    /// # struct Spawner;
+    /// # fn start_waves() {}
+    /// # let mut app = App::new();
+    /// fn spawn_wave(In(size): In<u32>, mut commands: Commands, spawner: Res<Spawner>) {}
+    ///
+    /// let (start_waves, spawn_wave) = start_waves.in_auto_sets_of(spawn_wave);
+    /// let spawn_wave_id = app.register_system(spawn_wave);
+    /// app.add_systems(Update, start_waves);
+    /// ```
+    fn in_auto_sets_of<T, N>(self, system: T) -> (ScheduleConfigs<S>, T)
+    where
+        T: InferableSystem<N>;
+}
+
+impl<I, M, SM> InferFlowOf<ScheduleSystem, (M, SM)> for I
+where
+    I: InferableSystem<M> + IntoSystem<(), (), SM>,
+{
+    fn in_auto_sets_of<T, N>(self, system: T) -> (ScheduleConfigs<ScheduleSystem>, T)
+    where
+        T: InferableSystem<N>,
+    {
+        let scheduled: ScheduleSystem =
+            Box::new(InfallibleSystemWrapper::new(IntoSystem::into_system(self)));
+        let configs = record_inferred(System::type_id(&*scheduled), || {
+            T::apply(I::apply(scheduled.into_configs()))
+        });
+        (configs, system)
+    }
+}
+
+impl<I, M, SM, E> InferFlowOf<ScheduleSystem, (M, SM, E)> for I
+where
+    I: InferableSystem<M> + IntoSystem<(), Result<(), E>, SM>,
+    E: Into<BevyError> + 'static,
+{
+    fn in_auto_sets_of<T, N>(self, system: T) -> (ScheduleConfigs<ScheduleSystem>, T)
+    where
+        T: InferableSystem<N>,
+    {
+        let scheduled =
+            IntoSystem::into_system(self.map(|result: Result<(), E>| result.map_err(Into::into)));
+        let configs = record_inferred(System::type_id(&scheduled), || {
+            T::apply(I::apply(scheduled.into_configs()))
+        });
+        (configs, system)
+    }
+}
@@ -0,0 +1,261 @@
+use std::any::TypeId;
+
+use bevy::ecs::schedule::{NodeId, Schedule, ScheduleGraph};
+
+use crate::{
+    cycles::{find_cycles, FlowCycle},
+    flow_graph::{target_name, FlowGraph},
+    markers::{AnyFlowSet, FlowAccess, FlowSet, FlowTarget},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// System found by [`FlowIntrospection`] queries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FlowSystem {
+    /// Node of the system in the schedule graph.
+    pub id: NodeId,
+    /// Name of the system.
+    pub name: String,
+}
+
+/// Flow set that a system belongs to, returned by [`FlowIntrospection::flows_of`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SystemFlowSet {
+    /// Node of the set in the schedule graph.
+    pub set: NodeId,
+    /// Whether the set is about reading, writing or modifying.
+    pub access: FlowAccess,
+    /// Data the set is created for, or `None` for [`ReadsAll`][crate::markers::ReadsAll] and
+    /// [`WritesAll`][crate::markers::WritesAll].
+    pub target: Option<FlowTarget>,
+    /// Full name of the target type if known, `*` for all data.
+    pub name: String,
+    /// Whether the membership was inferred by
+    /// [`.in_auto_sets()`][crate::automagic::InferFlow::in_auto_sets] or from the access of the
+    /// system.
+    pub inferred: bool,
+}
+
+/// Data with an ordering constraint on one of its flow sets, returned by
+/// [`FlowIntrospection::constrained_types`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConstrainedType {
+    /// Data the constrained set is created for.
+    pub target: FlowTarget,
+    /// Full name of the target type if known.
+    pub name: String,
+}
+
+/// Extension trait for [`Schedule`] and [`ScheduleGraph`] answering questions about their data
+/// flow, like "which systems write `Score`?".
+///
+/// Only membership in flow sets is taken into account, so systems in
+/// [`WritesAll`][crate::markers::WritesAll] are listed as writers only of types whose constraints
+/// nest it, like [`write_before_read`][crate::ordering::write_before_read]. Auto-sets inferred from
+/// access by [`AccessFlowPass`][crate::access::AccessFlowPass] and
+/// [`FlowSortingPass`][crate::access::FlowSortingPass] are included for a [`Schedule`] once it is
+/// built, but not for a bare [`ScheduleGraph`], which gives its systems away when it is built.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # #[derive(Resource)]
+/// # struct Score;
+/// # fn update_score(score: ResMut<Score>) {}
+/// # let mut app = App::new();
+/// # app.add_systems(Update, update_score.in_auto_sets());
+/// let schedule = app.get_schedule(Update).unwrap();
+/// let writers = schedule.writers_of::<Score>();
+/// assert_eq!(writers.len(), 1);
+/// assert!(writers[0].name.ends_with("update_score"));
+/// ```
+pub trait FlowIntrospection {
+    /// Returns systems in [`Writes<T>`][crate::markers::Writes] or
+    /// [`Modifies<T>`][crate::markers::Modifies].
+    fn writers_of<T: 'static>(&self) -> Vec<FlowSystem> {
+        self.writers_of_dyn(TypeId::of::<T>())
+    }
+
+    /// Returns systems in [`Reads<T>`][crate::markers::Reads].
+    fn readers_of<T: 'static>(&self) -> Vec<FlowSystem> {
+        self.readers_of_dyn(TypeId::of::<T>())
+    }
+
+    /// Returns systems in [`Modifies<T>`][crate::markers::Modifies].
+    fn modifiers_of<T: 'static>(&self) -> Vec<FlowSystem> {
+        self.modifiers_of_dyn(TypeId::of::<T>())
+    }
+
+    /// Returns systems in [`WritesDyn`][crate::markers::WritesDyn] or
+    /// [`ModifiesDyn`][crate::markers::ModifiesDyn] for `target`.
+    fn writers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem>;
+
+    /// Returns systems in [`ReadsDyn`][crate::markers::ReadsDyn] for `target`.
+    fn readers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem>;
+
+    /// Returns systems in [`ModifiesDyn`][crate::markers::ModifiesDyn] for `target`.
+    fn modifiers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem>;
+
+    /// Returns all flow sets that `system` belongs to.
+    fn flows_of(&self, system: NodeId) -> Vec<SystemFlowSet>;
+
+    /// Returns data whose flow sets are ordered by at least one constraint, in the order of
+    /// appearance.
+    fn constrained_types(&self) -> Vec<ConstrainedType>;
+
+    /// Returns cycles formed by flow constraints, explaining why the schedule can't be built.
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_sorting::prelude::*;
+    /// # let world = &mut World::new();
+    /// # let mut schedule = Schedule::default();
+    /// # schedule.add_systems(|| {});
+    /// if schedule.initialize(world).is_err() {
+    ///     for cycle in schedule.flow_cycles() {
+    ///         error!("{cycle}");
+    ///     }
+    /// }
+    /// ```
+    fn flow_cycles(&self) -> Vec<FlowCycle>;
+}
+
+impl FlowIntrospection for ScheduleGraph {
+    fn writers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem> {
+        FlowGraph::extract(self).writers_of_dyn(target)
+    }
+
+    fn readers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem> {
+        FlowGraph::extract(self).readers_of_dyn(target)
+    }
+
+    fn modifiers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem> {
+        FlowGraph::extract(self).modifiers_of_dyn(target)
+    }
+
+    fn flows_of(&self, system: NodeId) -> Vec<SystemFlowSet> {
+        FlowGraph::extract(self).flows_of(system)
+    }
+
+    fn constrained_types(&self) -> Vec<ConstrainedType> {
+        FlowGraph::extract(self).constrained_types()
+    }
+
+    fn flow_cycles(&self) -> Vec<FlowCycle> {
+        FlowGraph::extract(self).flow_cycles()
+    }
+}
+
+impl FlowIntrospection for Schedule {
+    fn writers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem> {
+        FlowGraph::from_schedule(self).writers_of_dyn(target)
+    }
+
+    fn readers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem> {
+        FlowGraph::from_schedule(self).readers_of_dyn(target)
+    }
+
+    fn modifiers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem> {
+        FlowGraph::from_schedule(self).modifiers_of_dyn(target)
+    }
+
+    fn flows_of(&self, system: NodeId) -> Vec<SystemFlowSet> {
+        FlowGraph::from_schedule(self).flows_of(system)
+    }
+
+    fn constrained_types(&self) -> Vec<ConstrainedType> {
+        FlowGraph::from_schedule(self).constrained_types()
+    }
+
+    fn flow_cycles(&self) -> Vec<FlowCycle> {
+        FlowGraph::from_schedule(self).flow_cycles()
+    }
+}
+
+impl FlowIntrospection for FlowGraph {
+    fn writers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem> {
+        let target = target.into();
+        members_of(
+            self,
+            &[
+                FlowSet::dynamic(FlowAccess::Write, target),
+                FlowSet::dynamic(FlowAccess::Modify, target),
+            ],
+        )
+    }
+
+    fn readers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem> {
+        members_of(self, &[FlowSet::dynamic(FlowAccess::Read, target.into())])
+    }
+
+    fn modifiers_of_dyn(&self, target: impl Into<FlowTarget>) -> Vec<FlowSystem> {
+        members_of(self, &[FlowSet::dynamic(FlowAccess::Modify, target.into())])
+    }
+
+    fn flows_of(&self, system: NodeId) -> Vec<SystemFlowSet> {
+        self.memberships
+            .iter()
+            .filter(|&&(member, _, _)| member == system)
+            .filter_map(|&(_, set, inferred)| {
+                let (_, kind, _) = self.set(set)?;
+                Some(SystemFlowSet {
+                    set,
+                    access: kind.access(),
+                    target: match kind {
+                        AnyFlowSet::Flow(flow) => Some(flow.target),
+                        AnyFlowSet::All(_) => None,
+                    },
+                    name: target_name(kind),
+                    inferred,
+                })
+            })
+            .collect()
+    }
+
+    fn constrained_types(&self) -> Vec<ConstrainedType> {
+        let mut types = Vec::<ConstrainedType>::new();
+        for &(before, after) in &self.constraints {
+            for node in [before, after] {
+                let Some((_, kind @ AnyFlowSet::Flow(set), _)) = self.set(node) else {
+                    continue;
+                };
+                if types.iter().all(|known| known.target != set.target) {
+                    types.push(ConstrainedType {
+                        target: set.target,
+                        name: target_name(kind),
+                    });
+                }
+            }
+        }
+        types
+    }
+
+    fn flow_cycles(&self) -> Vec<FlowCycle> {
+        find_cycles(self)
+    }
+}
+
+/// Returns the systems in any of the flow sets identified by `sets`, without duplicates.
+fn members_of(flow: &FlowGraph, sets: &[FlowSet]) -> Vec<FlowSystem> {
+    let nodes = flow
+        .sets
+        .iter()
+        .filter(|(_, kind, _)| sets.iter().any(|&set| *kind == AnyFlowSet::Flow(set)))
+        .map(|&(node, _, _)| node)
+        .collect::<Vec<_>>();
+    let mut members = Vec::<FlowSystem>::new();
+    for &(system, member_of, _) in &flow.memberships {
+        if !nodes.contains(&member_of) || members.iter().any(|member| member.id == system) {
+            continue;
+        }
+        if let Some((_, name)) = flow.systems.iter().find(|(id, _)| *id == system) {
+            members.push(FlowSystem {
+                id: system,
+                name: name.clone(),
+            });
+        }
+    }
+    members
+}
@@ -0,0 +1,102 @@
+use std::any::{type_name, TypeId};
+
+use bevy::prelude::*;
+
+use super::{FlowIntrospection, FlowSystem};
+use crate::prelude::{
+    write_before_read, FlowAccess, FlowTarget, InferFlow, IntoSystemProducing, IntoSystemRW,
+};
+
+#[test]
+fn readers_and_writers_are_listed() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (
+            score_writer.in_auto_sets(),
+            score_reset.produces::<Score>().in_auto_sets(),
+            score_reader.in_auto_sets(),
+            score_display.reads::<Score>(),
+        ),
+    );
+    let schedule = app.get_schedule(Update).unwrap();
+
+    let writers = schedule.writers_of::<Score>();
+    let mut writer_names = names(&writers);
+    writer_names.sort_unstable();
+    assert_eq!(writer_names, vec!["score_reset", "score_writer"]);
+    let modifiers = schedule.modifiers_of::<Score>();
+    assert_eq!(names(&modifiers), vec!["score_writer"]);
+    let readers = schedule.readers_of::<Score>();
+    let mut readers = names(&readers);
+    readers.sort_unstable();
+    assert_eq!(readers, vec!["score_display", "score_reader"]);
+
+    assert_eq!(
+        schedule.graph().writers_of_dyn(TypeId::of::<Score>()),
+        writers
+    );
+    assert_eq!(
+        schedule.graph().modifiers_of_dyn(TypeId::of::<Score>()),
+        modifiers
+    );
+    assert!(schedule.writers_of::<Unused>().is_empty());
+}
+
+#[test]
+fn flows_of_system_are_listed() {
+    let mut app = App::new();
+    app.add_systems(Update, score_writer.in_auto_sets().reads::<Unused>());
+    let schedule = app.get_schedule(Update).unwrap();
+    let writer = schedule.writers_of::<Score>()[0].id;
+
+    let mut flows = schedule.flows_of(writer);
+    flows.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(flows.len(), 2);
+    assert_eq!(flows[0].name, type_name::<Score>());
+    assert_eq!(flows[0].access, FlowAccess::Modify);
+    assert_eq!(
+        flows[0].target,
+        Some(FlowTarget::Type(TypeId::of::<Score>()))
+    );
+    assert!(flows[0].inferred);
+    assert_eq!(flows[1].name, type_name::<Unused>());
+    assert_eq!(flows[1].access, FlowAccess::Read);
+    assert!(!flows[1].inferred);
+}
+
+#[test]
+fn constrained_types_are_listed() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (score_writer.in_auto_sets(), score_reader.in_auto_sets()),
+    )
+    .configure_sets(Update, write_before_read::<Score>());
+    let schedule = app.get_schedule(Update).unwrap();
+
+    let types = schedule.constrained_types();
+    assert_eq!(types.len(), 1);
+    assert_eq!(types[0].target, FlowTarget::Type(TypeId::of::<Score>()));
+    assert_eq!(types[0].name, type_name::<Score>());
+}
+
+fn names(systems: &[FlowSystem]) -> Vec<&str> {
+    systems
+        .iter()
+        .map(|system| system.name.rsplit("::").next().unwrap())
+        .collect()
+}
+
+#[derive(Resource)]
+struct Score;
+
+struct Unused;
+
+fn score_writer(_score: ResMut<Score>) {}
+
+fn score_reset(_score: ResMut<Score>) {}
+
+fn score_reader(_score: Res<Score>) {}
+
+fn score_display(_score: Res<Score>) {}
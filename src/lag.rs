@@ -0,0 +1,288 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+};
+
+use bevy::{
+    app::{FixedMainScheduleOrder, MainScheduleOrder, RunFixedMainLoop},
+    ecs::{
+        component::Components,
+        schedule::{InternedScheduleLabel, NodeId, Schedule, ScheduleLabel, Schedules},
+    },
+    prelude::World,
+};
+use disqualified::ShortName;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    flow_graph::{event_resources, ordering_graph, reachable, target_name, FlowGraph, OrderNode},
+    markers::{AnyFlowSet, FlowAccess, FlowTarget},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Report of event readers that may see events one frame late, because they are not guaranteed
+/// to run after all writers of the event in the same frame.
+///
+/// Only events in [`EventFlow`][crate::markers::EventFlow] sets, which
+/// [`.in_auto_sets()`][crate::automagic::InferFlow::in_auto_sets] adds for `EventReader` and
+/// `EventWriter` parameters, and with their `Events<E>` resource registered in the world are
+/// analyzed. Only ordering known before the schedule is built is taken into account.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # let app = App::new();
+/// let report = EventLagReport::from_world(app.world());
+/// if !report.is_empty() {
+///     warn!("Events handled one frame late:\n{report}");
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct EventLagReport {
+    /// All reader and writer pairs that are not ordered, sorted.
+    pub lags: Vec<EventLag>,
+}
+
+/// Reader of an event that may run before its writer in a frame, reported by [`EventLagReport`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct EventLag {
+    /// Full name of the event type.
+    pub event: String,
+    /// Label of the schedule the reader runs in.
+    pub reader_schedule: String,
+    /// Name of the reader system.
+    pub reader: String,
+    /// Label of the schedule the writer runs in.
+    pub writer_schedule: String,
+    /// Name of the writer system.
+    pub writer: String,
+    /// Whether adding [`write_before_read`][crate::ordering::write_before_read] for the event
+    /// to the schedule would order the reader after the writer. It is never the case for systems
+    /// in different schedules, or if the reader is already ordered before the writer.
+    pub fixed_by_write_before_read: bool,
+}
+
+/// Readers and writers of each event in a schedule.
+struct EventUse {
+    name: String,
+    readers: Vec<NodeId>,
+    writers: Vec<NodeId>,
+}
+
+/// Event flow of a single schedule.
+struct ScheduleEvents {
+    label: String,
+    position: Option<(usize, usize)>,
+    events: HashMap<FlowTarget, EventUse>,
+    systems: Vec<(NodeId, String)>,
+}
+
+impl EventLagReport {
+    /// Creates the report for events read and written within `schedule` of `world`.
+    #[must_use]
+    pub fn from_schedule(world: &World, schedule: &Schedule) -> Self {
+        let events = schedule_events(world.components(), schedule, None);
+        let mut lags = lags_within(schedule, &events);
+        lags.sort();
+        Self { lags }
+    }
+
+    /// Creates the report for all schedules of `world`. Readers are also checked against writers
+    /// from other schedules of the main loop, like `FixedUpdate`, `Update` and `PostUpdate`, that
+    /// run later in the frame.
+    #[must_use]
+    pub fn from_world(world: &World) -> Self {
+        let Some(schedules) = world.get_resource::<Schedules>() else {
+            return Self::default();
+        };
+        let order = frame_order(world);
+
+        let analyzed = schedules
+            .iter()
+            .map(|(_, schedule)| {
+                let position = order.get(&schedule.label()).copied();
+                let events = schedule_events(world.components(), schedule, position);
+                (schedule, events)
+            })
+            .collect::<Vec<_>>();
+
+        let mut lags = analyzed
+            .iter()
+            .flat_map(|(schedule, events)| lags_within(schedule, events))
+            .collect::<Vec<_>>();
+        for (_, readers) in &analyzed {
+            for (_, writers) in &analyzed {
+                lags.extend(lags_across(readers, writers));
+            }
+        }
+        lags.sort();
+        lags.dedup();
+        Self { lags }
+    }
+
+    /// Returns `true` if nothing was reported.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lags.is_empty()
+    }
+}
+
+/// Returns positions of the main loop schedules in a frame. Fixed schedules share the position of
+/// [`RunFixedMainLoop`] and are ordered among themselves.
+fn frame_order(world: &World) -> HashMap<InternedScheduleLabel, (usize, usize)> {
+    let mut order = HashMap::new();
+    let Some(main) = world.get_resource::<MainScheduleOrder>() else {
+        return order;
+    };
+    for (index, &label) in main.labels.iter().enumerate() {
+        order.insert(label, (index, 0));
+        if label == RunFixedMainLoop.intern() {
+            let fixed = world
+                .get_resource::<FixedMainScheduleOrder>()
+                .map(|fixed| fixed.labels.as_slice())
+                .unwrap_or_default();
+            for (fixed_index, &fixed_label) in fixed.iter().enumerate() {
+                order.insert(fixed_label, (index, fixed_index + 1));
+            }
+        }
+    }
+    order
+}
+
+fn schedule_events(
+    components: &Components,
+    schedule: &Schedule,
+    position: Option<(usize, usize)>,
+) -> ScheduleEvents {
+    let flow = FlowGraph::from_schedule(schedule);
+    let event_targets = event_resources(schedule.graph(), components);
+    let mut events = HashMap::<FlowTarget, EventUse>::new();
+    for (id, kind, _) in &flow.sets {
+        let AnyFlowSet::Flow(set) = kind else {
+            continue;
+        };
+        if !event_targets.values().any(|&event| event == set.target) {
+            continue;
+        }
+        let event = events.entry(set.target).or_insert_with(|| EventUse {
+            name: target_name(kind),
+            readers: Vec::new(),
+            writers: Vec::new(),
+        });
+        if set.name.is_some() {
+            event.name = target_name(kind);
+        }
+        let members = flow
+            .memberships
+            .iter()
+            .filter(|&&(_, member_of, _)| member_of == *id)
+            .map(|&(system, _, _)| system);
+        match set.access {
+            FlowAccess::Read => event.readers.extend(members),
+            FlowAccess::Write | FlowAccess::Modify => event.writers.extend(members),
+        }
+    }
+    ScheduleEvents {
+        label: format!("{:?}", schedule.label()),
+        position,
+        events,
+        systems: flow.systems,
+    }
+}
+
+/// Returns readers not ordered after writers of the same event in `schedule`.
+fn lags_within(schedule: &Schedule, events: &ScheduleEvents) -> Vec<EventLag> {
+    let edges = ordering_graph(schedule.graph(), &FlowGraph::from_schedule(schedule));
+    let mut after = HashMap::new();
+    let mut runs_after = |from: NodeId, to: NodeId| {
+        after
+            .entry(from)
+            .or_insert_with(|| reachable(&edges, OrderNode::System(from)))
+            .contains(&OrderNode::System(to))
+    };
+
+    let mut lags = Vec::new();
+    for event in events.events.values() {
+        for &writer in &event.writers {
+            for &reader in &event.readers {
+                if reader == writer || runs_after(writer, reader) {
+                    continue;
+                }
+                lags.push(EventLag {
+                    event: event.name.clone(),
+                    reader_schedule: events.label.clone(),
+                    reader: system_name(events, reader),
+                    writer_schedule: events.label.clone(),
+                    writer: system_name(events, writer),
+                    fixed_by_write_before_read: !runs_after(reader, writer),
+                });
+            }
+        }
+    }
+    lags
+}
+
+/// Returns readers from `readers` running earlier in a frame than writers of the same event from
+/// `writers`.
+fn lags_across(readers: &ScheduleEvents, writers: &ScheduleEvents) -> Vec<EventLag> {
+    let (Some(reader_position), Some(writer_position)) = (readers.position, writers.position)
+    else {
+        return Vec::new();
+    };
+    if writer_position <= reader_position {
+        return Vec::new();
+    }
+
+    let mut lags = Vec::new();
+    for (target, read) in &readers.events {
+        let Some(written) = writers.events.get(target) else {
+            continue;
+        };
+        for &writer in &written.writers {
+            for &reader in &read.readers {
+                lags.push(EventLag {
+                    event: read.name.clone(),
+                    reader_schedule: readers.label.clone(),
+                    reader: system_name(readers, reader),
+                    writer_schedule: writers.label.clone(),
+                    writer: system_name(writers, writer),
+                    fixed_by_write_before_read: false,
+                });
+            }
+        }
+    }
+    lags
+}
+
+fn system_name(events: &ScheduleEvents, system: NodeId) -> String {
+    events
+        .systems
+        .iter()
+        .find(|(id, _)| *id == system)
+        .map(|(_, name)| name.clone())
+        .unwrap_or_default()
+}
+
+impl Display for EventLagReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for lag in &self.lags {
+            let event = ShortName(&lag.event);
+            write!(
+                f,
+                " -- `{}` in `{}` may read `{event}` written by `{}` in `{}` one frame late",
+                ShortName(&lag.reader),
+                lag.reader_schedule,
+                ShortName(&lag.writer),
+                lag.writer_schedule
+            )?;
+            if lag.fixed_by_write_before_read {
+                writeln!(f, ", add `write_before_read::<{event}>()` to fix it")?;
+            } else {
+                writeln!(f, ", `write_before_read::<{event}>()` would not fix it")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+use std::any::type_name;
+
+use bevy::prelude::*;
+
+use super::EventLagReport;
+use crate::prelude::{write_before_read, InferFlow, IntoSystemRW};
+
+#[test]
+fn unordered_reader_is_reported() {
+    let mut app = App::new();
+    app.add_event::<Alarm>();
+    app.add_systems(
+        Update,
+        (alarm_listener.in_auto_sets(), alarm_raiser.in_auto_sets()),
+    );
+
+    let report = EventLagReport::from_schedule(app.world(), app.get_schedule(Update).unwrap());
+
+    assert_eq!(report.lags.len(), 1);
+    let lag = &report.lags[0];
+    assert_eq!(lag.event, type_name::<Alarm>());
+    assert!(lag.reader.ends_with("alarm_listener"));
+    assert!(lag.writer.ends_with("alarm_raiser"));
+    assert!(lag.fixed_by_write_before_read);
+    assert!(report
+        .to_string()
+        .contains("add `write_before_read::<Alarm>()` to fix it"));
+}
+
+#[test]
+fn ordered_reader_is_not_reported() {
+    let mut app = App::new();
+    app.add_event::<Alarm>();
+    app.add_systems(
+        Update,
+        (alarm_listener.in_auto_sets(), alarm_raiser.in_auto_sets()),
+    )
+    .configure_sets(Update, write_before_read::<Alarm>());
+
+    assert!(
+        EventLagReport::from_schedule(app.world(), app.get_schedule(Update).unwrap()).is_empty()
+    );
+}
+
+#[test]
+fn reader_ordered_before_writer_cannot_be_fixed() {
+    let mut app = App::new();
+    app.add_event::<Alarm>();
+    app.add_systems(
+        Update,
+        (alarm_listener.in_auto_sets(), alarm_raiser.in_auto_sets()).chain(),
+    );
+
+    let report = EventLagReport::from_schedule(app.world(), app.get_schedule(Update).unwrap());
+
+    assert_eq!(report.lags.len(), 1);
+    assert!(!report.lags[0].fixed_by_write_before_read);
+}
+
+#[test]
+fn writers_in_later_schedules_are_reported() {
+    let mut app = App::new();
+    app.add_event::<Alarm>();
+    app.add_systems(Update, alarm_listener.in_auto_sets())
+        .add_systems(PostUpdate, alarm_raiser.in_auto_sets())
+        .add_systems(FixedUpdate, alarm_siren.in_auto_sets());
+
+    let report = EventLagReport::from_world(app.world());
+
+    assert_eq!(report.lags.len(), 1);
+    let lag = &report.lags[0];
+    assert_eq!(lag.reader_schedule, "Update");
+    assert_eq!(lag.writer_schedule, "PostUpdate");
+    assert!(lag.writer.ends_with("alarm_raiser"));
+    assert!(!lag.fixed_by_write_before_read);
+}
+
+#[test]
+fn events_of_other_apps_are_not_analyzed() {
+    let mut events = App::new();
+    events
+        .add_event::<Alarm>()
+        .add_systems(Update, alarm_listener.in_auto_sets());
+    assert!(EventLagReport::from_world(events.world()).is_empty());
+
+    let mut app = App::new();
+    app.add_event::<Alarm>().add_systems(
+        Update,
+        ((|| {}).reads::<Alarm>(), (|| {}).writes::<Alarm>()),
+    );
+
+    assert!(EventLagReport::from_world(app.world()).is_empty());
+}
+
+#[derive(Event)]
+struct Alarm;
+
+fn alarm_listener(_alarms: EventReader<Alarm>) {}
+
+fn alarm_raiser(_alarms: EventWriter<Alarm>) {}
+
+fn alarm_siren(_alarms: EventWriter<Alarm>) {}
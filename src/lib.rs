@@ -2,7 +2,17 @@
 //!
 //! # TL;DR
 //!
-//! ```rust
+//! ```rust,no_run
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Component)]
+//! # struct Awards;
+//! # #[derive(Component)]
+//! # struct Quest;
+//! # #[derive(Resource)]
+//! # struct Equipment;
+//! # #[derive(Event)]
+//! # struct LevelUpEvent;
 //! fn finish_quests(awards: Query<&mut Awards, With<Quest>>) {}
 //! fn update_equipment(awards: Query<&Awards>, equipment: ResMut<Equipment>) {}
 //! fn count_xp(awards: Query<&Awards>, writer: EventWriter<LevelUpEvent>) {}
@@ -11,7 +21,7 @@
 //!
 //! fn main() {
 //!     App::new()
-//!         .register_event::<LevelUpEvent>()
+//!         .add_event::<LevelUpEvent>()
 //!         .add_systems(
 //!             Update,
 //!             (
@@ -60,6 +70,16 @@
 //! When adding a system to the schedule, you can call [`.in_auto_sets()`][in_auto_sets] on it. The signature of the system will be analyzed. Then, the system will be added to appropriate system sets. For example
 //!
 //! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Resource)]
+//! # struct Something;
+//! # #[derive(Component)]
+//! # struct Data;
+//! # #[derive(Component)]
+//! # struct Marker;
+//! # #[derive(Event)]
+//! # struct Happening;
 //! fn my_system(
 //!     resource: ResMut<Something>,
 //!     query: Query<&mut Data, With<Marker>>,
@@ -67,14 +87,20 @@
 //! ) {
 //! }
 //!
-//! let app = App::new().add_systems(Update, my_system.in_auto_sets());
+//! let mut app = App::new();
+//! app.add_systems(Update, my_system.in_auto_sets());
 //! ```
 //!
-//! will create three auto-sets and add `my_system` to them. Those are [`Writes<Something>`][writes], [`Writes<Data>`][writes], [`Reads<Marker>`][reads] and [`Reads<Happening>`][reads]. Any mutable access to a resource or component or access to `EventWriter` is treated as a write. Any immutable access to a resource or component or `EventReader` is treated as a read.
+//! will create three auto-sets and add `my_system` to them. Those are [`Modifies<Something>`][modifies], [`Modifies<Data>`][modifies], [`Reads<Marker>`][reads] and [`Reads<Happening>`][reads]. Any mutable access to a resource or component is treated as a modification, and access to `EventWriter` as a write. Any immutable access to a resource or component or `EventReader` is treated as a read.
 //!
 //! It is possible to infer data flow for each system in a tuple using [`each_in_auto_set`][each_in_auto_set]. There is no need for repeated calls of [`.in_auto_sets()`][in_auto_sets].:
 //!
 //! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # fn system1() {}
+//! # fn system2() {}
+//! # fn system3() {}
 //! App::new()
 //!     .add_systems(Update,
 //!          (
@@ -88,6 +114,11 @@
 //! It is equivalent to
 //!
 //! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # fn system1() {}
+//! # fn system2() {}
+//! # fn system3() {}
 //! App::new()
 //!     .add_systems(Update,
 //!          (
@@ -102,13 +133,21 @@
 //!
 //! ### Constraints
 //!
-//! There are two kinds of constraints:
+//! There are three kinds of constraints:
 //! - [`read_before_write::<SomeType>()`][read_before_write] means that all systems that reads from `SomeType` (ie. is in the `Reads<SomeType>` auto-set) are executed before any system that writes to `SomeType`.
 //! - [`write_before_read::<SomeType>()`][write_before_read] means that all system that writes to `SomeType` will be executed before any system that reads from `SomeType`.
+//! - [`write_before_modify_before_read::<SomeType>()`][write_before_modify_before_read] means that systems producing `SomeType` are executed first, then systems modifying it, and then systems reading it.
 //!
 //! You can specify those constraints by calling the `configure_sets` function of `App`. So, continuing the snippet from the previous section, you can write:
 //!
 //! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Component)]
+//! # struct Marker;
+//! # #[derive(Event)]
+//! # struct Happening;
+//! # let mut app = App::new();
 //! app.configure_sets(Update, (write_before_read::<Happening>(), read_before_write::<Marker>()));
 //! ```
 //!
@@ -117,15 +156,209 @@
 //! Not everything can be inferred from the system's function signature. Sometimes we use `Commands` to add new resource or component to existing entity. If you want to make constraints besed on those resources or entities you need to mark the data flow manually using [`.reads()`][readsfn] and [`.writes()`][writesfn] functions.
 //!
 //! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # struct NewResource;
+//! # struct SomeComponent;
+//! # fn system1() {}
+//! # fn system2() {}
+//! # let mut app = App::new();
 //! app.add_systems(Update,
 //!     (
 //!         system1.in_auto_sets().writes::<NewResource>(),
 //!         system2.reads::<NewResource>().writes::<SomeComponent>()
 //!     )
-//! )
+//! );
 //! ```
 //! `system1` will have its normal auto-sets inferred and also will be added to `Writes<NewResource>`. `system2` will be added to `Reads<NewResource>` and `Writes<SomeComponent>` without any auto-sets inferred from the function signature.
 //!
+//! Targets known only at runtime, like scripting components or types coming from reflection, can be marked with [`.reads_dyn()`][reads_dynfn] and [`.writes_dyn()`][writes_dynfn], taking either `TypeId` or `FlowTarget`. Sets created for a `TypeId` are the same sets as their typed counterparts, so `ReadsDyn::new(TypeId::of::<T>())` and `Reads<T>` can be used interchangeably:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Component)]
+//! # struct Scripted;
+//! # fn script_system() {}
+//! # let mut app = App::new();
+//! # let component_id = app.world_mut().register_component::<Scripted>();
+//! let target = FlowTarget::component(app.world().components(), component_id);
+//! app.add_systems(Update, script_system.writes_dyn(target))
+//!     .configure_sets(Update, write_before_read_dyn(target));
+//! ```
+//!
+//! A `ComponentId` is turned into a target with [`FlowTarget::component`][flow_target_component], which uses the `TypeId` of the component if it has one, so the sets are the same as the typed ones and order systems with `.in_auto_sets()` too. Only components without a rust type, like the ones registered with `register_component_with_descriptor`, get sets keyed by their `ComponentId`.
+//!
+//! ### Producers, modifiers and readers
+//!
+//! Most systems with mutable access also read the value before changing it, so [`.in_auto_sets()`][in_auto_sets] puts them in [`Modifies<T>`][modifies]. Systems that only overwrite the value can be declared as producers with [`.produces::<T>()`][produces], called before [`.in_auto_sets()`][in_auto_sets], which puts them in [`Writes<T>`][writes] instead. Systems with manually specified flow use [`.modifies()`][modifiesfn] for the same purpose. Then [`write_before_modify_before_read::<T>()`][write_before_modify_before_read] runs producers first, then modifiers, and readers last:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Component)]
+//! # struct Velocity;
+//! # fn reset_velocity(velocities: Query<&mut Velocity>) {}
+//! # fn apply_gravity(velocities: Query<&mut Velocity>) {}
+//! # fn move_bodies(velocities: Query<&Velocity>) {}
+//! # let mut app = App::new();
+//! app.add_systems(Update,
+//!     (
+//!         reset_velocity.produces::<Velocity>().in_auto_sets(),
+//!         apply_gravity.in_auto_sets(),
+//!         move_bodies.in_auto_sets(),
+//!     )
+//! )
+//! .configure_sets(Update, write_before_modify_before_read::<Velocity>());
+//! ```
+//!
+//! [`write_before_read`][write_before_read] and [`read_before_write`][read_before_write] treat modifiers as writers.
+//!
+//! **Breaking change:** earlier versions put systems with mutable access in `Writes<T>`. Constraints like `write_before_read` and `read_before_write` order modifiers like writers, so they keep working, but ordering against `Writes<T>` directly, like `.after(Writes::<T>::default())`, now only covers producers. Order against `Modifies<T>` as well, or declare the systems as producers with `.produces::<T>()`.
+//!
+//! ### Custom system parameters
+//!
+//! Structs deriving `SystemParam` can take part in flow inference too. Just derive `AutoSetArg` next to it, and auto-sets of each field will be applied to the systems using the struct:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # use bevy::ecs::system::SystemParam;
+//! # #[derive(Resource)]
+//! # struct Score;
+//! # #[derive(Event)]
+//! # struct KillEvent;
+//! #[derive(SystemParam, AutoSetArg)]
+//! struct Scoring<'w, 's> {
+//!     score: ResMut<'w, Score>,
+//!     kills: EventReader<'w, 's, KillEvent>,
+//! }
+//!
+//! fn update_score(scoring: Scoring) {}
+//!
+//! # let mut app = App::new();
+//! app.add_systems(Update, update_score.in_auto_sets());
+//! ```
+//!
+//! `update_score` will be added to `Modifies<Score>` and `Reads<KillEvent>`.
+//!
+//! The same goes for custom query data. Derive `AutoSetArgInQuery` next to `QueryData`:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # use bevy::ecs::query::QueryData;
+//! # #[derive(Component)]
+//! # struct Health;
+//! #[derive(QueryData, AutoSetArgInQuery)]
+//! #[query_data(mutable)]
+//! struct PlayerQuery {
+//!     transform: &'static mut Transform,
+//!     health: &'static Health,
+//! }
+//! ```
+//!
+//! `Query<PlayerQuery>` will put the system in `Modifies<Transform>` and `Reads<Health>`, while `Query<PlayerQueryReadOnly>` will put it in `Reads<Transform>` and `Reads<Health>`.
+//!
+//! Similarly, custom query filters need `AutoSetArgInQueryFilter` derived next to `QueryFilter`:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # use bevy::ecs::query::QueryFilter;
+//! # #[derive(Component)]
+//! # struct Enemy;
+//! # #[derive(Component)]
+//! # struct Dead;
+//! #[derive(QueryFilter, AutoSetArgInQueryFilter)]
+//! struct EnemyFilter {
+//!     with: With<Enemy>,
+//!     without: Without<Dead>,
+//! }
+//! ```
+//!
+//! `Query<&Transform, EnemyFilter>` will put the system in `Reads<Transform>`, `Reads<Enemy>` and `Reads<Dead>`.
+//!
+//! ### Access-based inference
+//!
+//! Inference based on the signature needs an implementation for each parameter type. If you are using boxed systems, systems built with `SystemParamBuilder` or third-party parameters, you can infer auto-sets from the access the system reports after initialization instead. Mark the system with [`.in_access_sets()`][in_access_sets] and add [`AccessFlowPass`][access_flow_pass] to the schedule:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # use bevy::ecs::system::ScheduleSystem;
+//! # fn my_system() {}
+//! # fn other_system() -> Result { Ok(()) }
+//! # let boxed_system: ScheduleSystem = Box::new(IntoSystem::into_system(other_system));
+//! # let mut app = App::new();
+//! app.add_systems(Update, (boxed_system.in_access_sets(), my_system.in_access_sets()))
+//!     .edit_schedule(Update, |schedule| {
+//!         schedule.add_build_pass(AccessFlowPass);
+//!     });
+//! ```
+//!
+//! Such systems are not added to any set, but are ordered by the pass as if they were members of the auto-sets. Introspection, exports, manifests and the lint passes treat them as members too, once the schedule is built and their access is known. Only actual access is analyzed, so filters like `With<Marker>` don't put the system in `Reads<Marker>`.
+//!
+//! ### Zero-annotation mode
+//!
+//! If you'd rather not annotate systems at all, add [`FlowSortingPlugin`][flow_sorting_plugin] for the schedules you want to be sorted. It installs [`FlowSortingPass`][flow_sorting_pass], which infers auto-sets of every system in the schedule from its access when the schedule is built:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Event)]
+//! # struct LevelUpEvent;
+//! # fn count_xp(writer: EventWriter<LevelUpEvent>) {}
+//! # fn update_stats(reader: EventReader<LevelUpEvent>) {}
+//! # fn run_levelup_animation(reader: EventReader<LevelUpEvent>) {}
+//! # let mut app = App::new();
+//! app.add_plugins(FlowSortingPlugin::new(Update).with_schedule(FixedUpdate))
+//!     .add_systems(Update, (count_xp, update_stats, run_levelup_animation))
+//!     .configure_sets(
+//!         Update,
+//!         (EventFlow::of::<LevelUpEvent>(), write_before_read::<LevelUpEvent>()),
+//!     );
+//! ```
+//!
+//! Events are accessed through their `Events<E>` resource, which the passes match with flow sets of `E` only for events in an [`EventFlow`][event_flow] set of the schedule. `.in_auto_sets()` adds it for `EventReader` and `EventWriter` parameters; in schedules where no system has inferred auto-sets, configure it yourself like above.
+//!
+//! ### Default order for events
+//!
+//! Configuring `write_before_read` for every event type gets repetitive. Add [`SortingPlugin`][sorting_plugin] instead, and writers of every event will run before its readers in the given schedules. The policy applies to events read or written by systems with `.in_auto_sets()`, and can be overridden for a single type with [`FlowOrder`][flow_order]:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # let mut app = App::new();
+//! app.add_plugins(
+//!     SortingPlugin::new(Update)
+//!         .with_schedule(FixedUpdate)
+//!         .with_order::<AppExit>(FlowOrder::ReadBeforeWrite),
+//! );
+//! ```
+//!
+//! Types with any constraint configured explicitly in a schedule keep that constraint.
+//!
+//! ### Declaring the order next to the type
+//!
+//! The order can also be declared where the type is defined, with the `#[flow(...)]` attribute implementing [`FlowPolicy`][flow_policy]. Register the types, which adds [`FlowPolicyPlugin`][flow_policy_plugin] applying each policy to every schedule where the type is read or written. Schedules created after the app is finished get the policies when added with [`.add_flow_schedule()`][add_flow_schedule] instead of `add_schedule`:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! #[derive(Event)]
+//! #[flow(write_before_read)]
+//! struct LevelUpEvent;
+//! # #[derive(Event)]
+//! # #[flow(read_before_write)]
+//! # struct GameOverEvent;
+//!
+//! # let mut app = App::new();
+//! app.register_flow_policy::<(LevelUpEvent, GameOverEvent)>();
+//! ```
+//!
+//! The attribute takes `write_before_read`, `read_before_write` or `unordered`.
+//!
 //! ### What types can be used in flow markers?
 //!
 //! Automatic inference creates auto-sets for components, resources, and events. In manual markers, you can use them, but also any rust type (as long as it is `'static`). For example, if you want to group all systems manipulating or reading stats of the player, you can create unit type `PlayerStats` and use it in markers: `.writes::<PlayerStats>` and `.reads::<PlayerStats>`.
@@ -134,14 +367,18 @@
 //!
 //! ### Avoiding one-frame-off event handling
 //!
-//! ```rust
+//! ```rust,no_run
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Event)]
+//! # struct LevelUpEvent;
 //! fn count_xp(writer: EventWriter<LevelUpEvent>) {}
-//! fn update_stats(reader: EventReader<LevelUpEvent) {}
+//! fn update_stats(reader: EventReader<LevelUpEvent>) {}
 //! fn run_levelup_animation(reader: EventReader<LevelUpEvent>) {}
 //!
 //! fn main() {
 //!     App::new()
-//!         .register_event::<LevelUpEvent>()
+//!         .add_event::<LevelUpEvent>()
 //!         .add_systems(Update, (
 //!             count_xp,
 //!             update_stats,
@@ -155,10 +392,18 @@
 //!
 //! ### Correct order of resource initialization
 //!
-//! ```rust
+//! ```rust,no_run
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Resource)]
+//! # struct MapConfig;
+//! # #[derive(Resource)]
+//! # struct WorldGenAlgorithm;
+//! # #[derive(Resource)]
+//! # struct WorldMap;
 //! fn read_map_config(mut commands: Commands) {
 //!     let config: MapConfig = todo!();
-//!     command.insert_resource(config);
+//!     commands.insert_resource(config);
 //! }
 //!
 //! fn select_gen_algorithm(
@@ -179,10 +424,10 @@
 //! }
 //!
 //! fn main() {
-//!     App:new()
+//!     App::new()
 //!         .add_systems(Startup, (
 //!             generate_map.in_auto_sets(),
-//!             select_gen_algorithm.in_auto_sets().writes::<WorldGenAlgorithm>,
+//!             select_gen_algorithm.in_auto_sets().writes::<WorldGenAlgorithm>(),
 //!             read_map_config.writes::<MapConfig>(),
 //!         ))
 //!         .configure_sets(Startup, (
@@ -197,30 +442,371 @@
 //!
 //! ## What kind of systems are supported?
 //!
-//! For now, only systems defined as closures or function references are supported. In the future, I want to support piped systems. Due to Bevy's design, not much more is currently possible.
+//! Systems defined as closures or function references are supported, both infallible and returning `Result`, as well as piped systems. For a piped system, auto-sets inferred from both sides of the pipe are combined:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # fn parse_input() -> Result<(), String> { Ok(()) }
+//! # fn handle_errors(In(result): In<Result<(), String>>) {}
+//! # let mut app = App::new();
+//! app.add_systems(Update, parse_input.pipe(handle_errors).in_auto_sets());
+//! ```
+//!
+//! Systems taking `In<T>`, `InRef<T>` or `InMut<T>` can't be added to a schedule directly. As the receiving end of a pipe, they are covered by the inference of the whole pipe. When they are registered with `register_system` and run through their `SystemId`, put the scheduled system running them in the same auto-sets with [`.in_auto_sets_of()`][in_auto_sets_of], which infers the auto-sets of the scheduled system too. The input-taking system is handed back untouched, so it can still be run with different inputs:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Resource)]
+//! # struct Spawner;
+//! # fn start_waves() {}
+//! # let mut app = App::new();
+//! fn spawn_wave(In(size): In<u32>, mut commands: Commands, spawner: Res<Spawner>) {}
+//!
+//! let (start_waves, spawn_wave) = start_waves.in_auto_sets_of(spawn_wave);
+//! let spawn_wave_id = app.register_system(spawn_wave);
+//! app.add_systems(Update, start_waves);
+//! ```
+//!
+//! Exclusive systems, taking `&mut World`, can touch anything, so [`.in_auto_sets()`][in_auto_sets] puts them in the [`WritesAll`][writes_all] set. Similarly, systems taking `&World` are put in [`ReadsAll`][reads_all]. Constraints treat them as writing and reading every type, so `write_before_read::<Health>()` runs exclusive systems before readers of `Health`, and systems taking `&World` after writers of `Health`:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Component)]
+//! # struct Health;
+//! # fn save_game(world: &mut World) {}
+//! # fn apply_damage(health: Query<&mut Health>) {}
+//! # let mut app = App::new();
+//! app.add_systems(Update, (save_game.in_auto_sets(), apply_damage.in_auto_sets()))
+//!     .configure_sets(Update, write_before_read::<Health>());
+//! ```
+//!
+//! If you know what an exclusive system writes to, you can declare it with [`.exclusive_flow()`][exclusive_flow] instead. The system will be put in `Writes<T>` for each type of the tuple, and ordered by constraints like any other writer:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Resource)]
+//! # struct Level;
+//! # #[derive(Resource)]
+//! # struct Navmesh;
+//! # fn rebuild_level(world: &mut World) {}
+//! # let mut app = App::new();
+//! app.add_systems(Update, rebuild_level.exclusive_flow::<(Level, Navmesh)>());
+//! ```
+//!
+//! ## How can I see the data flow?
+//!
+//! Use [`flow_to_dot`][flow_to_dot] or [`flow_to_mermaid`][flow_to_mermaid] to render systems of a schedule, auto-sets they belong to and constraints between them:
+//!
+//! ```rust,no_run
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # fn main() -> std::io::Result<()> {
+//! # let mut app = App::new();
+//! # app.add_systems(Update, || {});
+//! std::fs::write("update.dot", flow_to_dot(app.get_schedule(Update).unwrap()))?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! For tooling and code review, [`FlowManifest`][flow_manifest] describes the same data in a serializable form. It lists systems of each schedule with their inferred and manual reads and writes, as well as the configured constraints:
+//!
+//! ```rust,no_run
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let app = App::new();
+//! let manifest = FlowManifest::from_schedules(app.world().resource::<Schedules>());
+//! std::fs::write("flow.json", serde_json::to_string_pretty(&manifest)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! To assert on the data flow in tests or debug tools, use [`FlowIntrospection`][flow_introspection]. It is implemented for `Schedule` and `ScheduleGraph` and answers questions like "which systems write `Score`?":
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Resource)]
+//! # struct Score;
+//! # fn update_score(score: ResMut<Score>) {}
+//! # fn show_score(score: Res<Score>) {}
+//! # let mut app = App::new();
+//! # app.add_systems(Update, (update_score, show_score).each_in_auto_sets());
+//! let schedule = app.get_schedule(Update).unwrap();
+//! let writers = schedule.writers_of::<Score>();
+//! let readers = schedule.readers_of::<Score>();
+//! let flows = schedule.flows_of(writers[0].id);
+//! let constrained = schedule.constrained_types();
+//! ```
+//!
+//! ## Which types take part in the flow?
+//!
+//! Add [`FlowRegistryPlugin`][flow_registry_plugin] to keep a [`FlowRegistry`][flow_registry] resource listing every type appearing in a `Reads` or `Writes` set, with its name, kind (event, resource, component, non-send resource or manual marker) and the schedules it appears in. It is filled from all schedules when the app is finished, and updated whenever a schedule is built:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # fn count_xp() {}
+//! # let mut app = App::new();
+//! app.add_plugins(FlowRegistryPlugin)
+//!     .add_systems(Update, count_xp.in_auto_sets());
+//! app.finish();
+//!
+//! for flow_type in app.world().resource::<FlowRegistry>().iter() {
+//!     info!("{} ({:?}) in {:?}", flow_type.name, flow_type.kind, flow_type.schedules);
+//! }
+//! ```
+//!
+//! ## Are writers of the same data ordered?
+//!
+//! No. Constraints like [`write_before_read`][write_before_read] order writers against readers, but two systems in the same `Writes<T>` set still run in arbitrary order relative to each other. Add [`UnorderedWritersPass`][unordered_writers_pass] to a schedule to report such pairs when it is built. It only looks at ordering configured on the schedule, not at edges added by other build passes. It can log them with `LogLevel::Warn`, fail the build with an ambiguity error with `LogLevel::Error` or be disabled with `LogLevel::Ignore`. Types whose writers may run in any order can be allowed explicitly:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # use bevy::ecs::schedule::LogLevel;
+//! # #[derive(Resource)]
+//! # struct Score;
+//! # let mut app = App::new();
+//! app.edit_schedule(Update, |schedule| {
+//!     schedule.add_build_pass(UnorderedWritersPass::new(LogLevel::Error).allow::<Score>());
+//! });
+//! ```
+//!
+//! If writers need to run in the same order every time, for example in a lockstep multiplayer game, call [`app.serialize_writers::<T>(schedule)`][serialize_writers], which adds [`SerializeWritersPass`][serialize_writers_pass] to the schedule. It chains all systems writing or modifying `T` by their name, or by the priority given with [`.writes_with_priority::<T>(n)`][writes_with_priority], lower running first. Other systems stay parallel:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Component)]
+//! # struct Health;
+//! # fn apply_damage(health: Query<&mut Health>) {}
+//! # fn regenerate() {}
+//! # let mut app = App::new();
+//! app.add_systems(Update, (apply_damage.in_auto_sets(), regenerate.writes_with_priority::<Health>(-1)))
+//!     .serialize_writers::<Health>(Update);
+//! ```
+//!
+//! ## Is there any flow leading nowhere?
+//!
+//! Refactors tend to leave behind an `EventReader<E>` without any `EventWriter<E>`, or a `ResMut<R>` that nobody reads. [`DeadFlowReport`][dead_flow_report] lists types that are only read, types that are only written and constraints ordering sets without any systems:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # let app = App::new();
+//! let report = DeadFlowReport::from_schedules(app.world().resource::<Schedules>());
+//! if !report.is_empty() {
+//!     warn!("Dead data flow:\n{report}");
+//! }
+//! ```
+//!
+//! Use `DeadFlowReport::from_schedule` to check a single schedule only.
+//!
+//! ## Are my events still handled one frame late?
+//!
+//! [`EventLagReport`][event_lag_report] finds event readers that are not guaranteed to run after all writers of the event in the same frame. It checks systems within each schedule, as well as readers in schedules of the main loop, like `FixedUpdate`, `Update` and `PostUpdate`, against writers in schedules running later in the frame. For each reader, the report tells whether adding `write_before_read::<E>()` would fix it:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # let app = App::new();
+//! let report = EventLagReport::from_world(app.world());
+//! if !report.is_empty() {
+//!     warn!("Events handled one frame late:\n{report}");
+//! }
+//! ```
+//!
+//! ## Can I check that the order holds at runtime?
+//!
+//! Add [`FlowVerificationPlugin`][flow_verification_plugin] in debug builds. It instruments systems in flow sets, records when they start and finish and reports any system that started before the systems it is ordered after have finished. By default, a violation panics; use `.with_level(LogLevel::Warn)` to only log it. All violations are also listed by the `FlowVerification` resource:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # let mut app = App::new();
+//! app.add_plugins(FlowVerificationPlugin::new(Update).with_schedule(FixedUpdate));
+//! ```
+//!
+//! ## Do my declarations match what systems actually access?
+//!
+//! Manual `.reads::<T>()` and `.writes::<T>()` calls can drift from the parameters of a system. Add [`AccessCoveragePass`][access_coverage_pass] to a schedule to compare the access of each system with the flow sets it belongs to, when the schedule is built. It reports access that is not declared, like `ResMut<Foo>` in a system added without `.in_auto_sets()`, and manual declarations that the system never uses:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # use bevy::ecs::schedule::LogLevel;
+//! # let mut app = App::new();
+//! app.edit_schedule(Update, |schedule| {
+//!     schedule.add_build_pass(AccessCoveragePass::new(LogLevel::Warn).allow::<Time>());
+//! });
+//! ```
+//!
+//! Keep in mind that changes done through `Commands` are not part of the access of a system.
+//!
+//! ## Why does my schedule report a dependency cycle?
+//!
+//! Conflicting constraints, like [`write_before_read::<A>()`][write_before_read] together with [`read_before_write::<A>()`][read_before_write], or two systems that read each other's writes, make the schedule impossible to sort. Bevy reports such a cycle in terms of anonymous-looking sets. Call [`.flow_cycles()`][flow_cycles] to get it explained in terms of data flow, together with a suggestion which constraint to drop:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # let mut app = App::new();
+//! # app.add_systems(Update, || {});
+//! for cycle in app.get_schedule(Update).unwrap().flow_cycles() {
+//!     // `physics` writes `Velocity` and reads `Collision`; `collide` writes `Collision` and
+//!     // reads `Velocity`; constraints ... form a cycle. Consider dropping ...
+//!     error!("{cycle}");
+//! }
+//! ```
+//!
+//! To get the explanation in the build error itself, add [`FlowCyclePass`][flow_cycle_pass] to the schedule:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # let mut app = App::new();
+//! app.edit_schedule(Update, |schedule| {
+//!     schedule.add_build_pass(FlowCyclePass);
+//! });
+//! ```
+//!
+//! Cycles going through systems then fail the build with the data-flow explanation instead of Bevy's message. Cycles between the sets themselves are detected by Bevy before build passes run, so they keep Bevy's message and only `.flow_cycles()` explains them.
+//!
+//! If one of the systems deliberately uses the value from the previous frame, infer its auto-sets with [`.in_auto_sets_except::<(T,)>()`][in_auto_sets_except] instead. It leaves out the sets of the listed types, so constraints on them don't order the system at all:
+//!
+//! ```rust
+//! # use bevy::prelude::*;
+//! # use bevy_sorting::prelude::*;
+//! # #[derive(Component)]
+//! # struct Velocity;
+//! # fn physics() {}
+//! # fn collide(velocities: Query<&Velocity>) {}
+//! # let mut app = App::new();
+//! app.add_systems(Update, (physics.in_auto_sets(), collide.in_auto_sets_except::<(Velocity,)>()));
+//! ```
 //!
 //! ## Can I mix regular system sets with auto-sets?
 //!
 //! Of course! There is a small caveat, however. All `in_set()` calls must be called after the [`.in_auto_sets()`][in_auto_sets]. Otherwise, you will get a compilation error. This is due to Bevy's design, and I don't think there is anything I can do about it. You can, however, freely mix `.in_set()` calls with any combination of `.writes()` and `.reads()` calls.
 //!
 //! [in_auto_sets]:crate::automagic::InferFlow::in_auto_sets
+//! [in_auto_sets_of]:crate::input::InferFlowOf::in_auto_sets_of
+//! [in_auto_sets_except]:crate::automagic::InferFlow::in_auto_sets_except
 //! [writes]:crate::markers::Writes
 //! [reads]:crate::markers::Reads
+//! [modifies]:crate::markers::Modifies
 //! [writesfn]:crate::markers::IntoSystemRW::writes
 //! [readsfn]:crate::markers::IntoSystemRW::reads
+//! [modifiesfn]:crate::markers::IntoSystemRW::modifies
+//! [produces]:crate::produce::IntoSystemProducing::produces
+//! [writes_dynfn]:crate::markers::IntoSystemRW::writes_dyn
+//! [reads_dynfn]:crate::markers::IntoSystemRW::reads_dyn
+//! [flow_target_component]:crate::markers::FlowTarget::component
 //! [write_before_read]:crate::ordering::write_before_read
+//! [write_before_modify_before_read]:crate::ordering::write_before_modify_before_read
 //! [read_before_write]:crate::ordering::read_before_write
 //! [each_in_auto_set]:crate::automagic::InferFlowEach::each_in_auto_sets
+//! [writes_all]:crate::markers::WritesAll
+//! [reads_all]:crate::markers::ReadsAll
+//! [exclusive_flow]:crate::markers::ExclusiveFlow::exclusive_flow
+//! [in_access_sets]:crate::access::InferAccessFlow::in_access_sets
+//! [access_flow_pass]:crate::access::AccessFlowPass
+//! [flow_sorting_plugin]:crate::access::FlowSortingPlugin
+//! [event_flow]:crate::markers::EventFlow
+//! [flow_sorting_pass]:crate::access::FlowSortingPass
+//! [flow_to_dot]:crate::export::flow_to_dot
+//! [flow_to_mermaid]:crate::export::flow_to_mermaid
+//! [flow_manifest]:crate::manifest::FlowManifest
+//! [flow_introspection]:crate::introspect::FlowIntrospection
+//! [flow_cycles]:crate::introspect::FlowIntrospection::flow_cycles
+//! [flow_cycle_pass]:crate::cycles::FlowCyclePass
+//! [unordered_writers_pass]:crate::lint::UnorderedWritersPass
+//! [serialize_writers]:crate::serialize::SerializeWritersAppExt::serialize_writers
+//! [serialize_writers_pass]:crate::serialize::SerializeWritersPass
+//! [writes_with_priority]:crate::markers::IntoSystemRW::writes_with_priority
+//! [dead_flow_report]:crate::lint::DeadFlowReport
+//! [event_lag_report]:crate::lag::EventLagReport
+//! [flow_verification_plugin]:crate::verify::FlowVerificationPlugin
+//! [access_coverage_pass]:crate::lint::AccessCoveragePass
+//! [sorting_plugin]:crate::policy::SortingPlugin
+//! [flow_order]:crate::policy::FlowOrder
+//! [flow_policy]:crate::policy::FlowPolicy
+//! [flow_policy_plugin]:crate::policy::FlowPolicyPlugin
+//! [flow_registry]:crate::registry::FlowRegistry
+//! [flow_registry_plugin]:crate::registry::FlowRegistryPlugin
+//! [add_flow_schedule]:crate::schedules::FlowScheduleExt::add_flow_schedule
+
+extern crate self as bevy_sorting;
 
+mod access;
 mod automagic;
+mod cycles;
+mod export;
+mod flow_graph;
+mod input;
+mod introspect;
+mod lag;
+mod lint;
+mod manifest;
 mod markers;
 mod ordering;
+mod policy;
+mod produce;
+mod registry;
+mod schedules;
+mod serialize;
+mod verify;
 
 #[cfg(test)]
 mod tests;
 
 pub mod prelude {
-    pub use crate::automagic::{InferFlow, InferFlowEach};
-    pub use crate::markers::{IntoSystemRW, Reads, Writes};
-    pub use crate::ordering::{read_before_write, write_before_read};
+    pub use crate::access::{
+        AccessFlowPass, AccessInferred, FlowSortingPass, FlowSortingPlugin, InferAccessFlow,
+    };
+    pub use crate::automagic::{
+        AutoSetArg, AutoSetArgInQuery, AutoSetArgInQueryFilter, IgnoredTypes, InferFlow,
+        InferFlowEach, InferableSystem,
+    };
+    pub use crate::cycles::{CycleSystem, FlowCycle, FlowCyclePass};
+    pub use crate::export::{flow_to_dot, flow_to_mermaid};
+    pub use crate::input::InferFlowOf;
+    pub use crate::introspect::{ConstrainedType, FlowIntrospection, FlowSystem, SystemFlowSet};
+    pub use crate::lag::{EventLag, EventLagReport};
+    pub use crate::lint::{
+        AccessCoveragePass, DeadFlowReport, EmptyConstraint, UnorderedWritersPass,
+    };
+    pub use crate::manifest::{FlowConstraint, FlowManifest, ScheduleFlow, SystemFlow};
+    pub use crate::markers::{
+        EventFlow, ExclusiveFlow, FlowAccess, FlowTarget, FlowTypes, IntoSystemRW, Modifies,
+        ModifiesDyn, Reads, ReadsAll, ReadsDyn, Writes, WritesAll, WritesDyn,
+    };
+    pub use crate::ordering::{
+        read_before_write, read_before_write_dyn, write_before_modify_before_read,
+        write_before_modify_before_read_dyn, write_before_read, write_before_read_dyn,
+    };
+    pub use crate::policy::{
+        FlowOrder, FlowPolicy, FlowPolicyAppExt, FlowPolicyPlugin, FlowPolicyTypes, SortingPlugin,
+    };
+    pub use crate::produce::{IntoProducing, IntoSystemProducing};
+    pub use crate::registry::{FlowKind, FlowRegistry, FlowRegistryPlugin, FlowType};
+    pub use crate::schedules::FlowScheduleExt;
+    pub use crate::serialize::{
+        SerializeWritersAppExt, SerializeWritersPass, SerializedWriters, WritePriority,
+    };
+    pub use crate::verify::{FlowVerification, FlowVerificationPlugin, FlowViolation};
+    pub use bevy_sorting_macros::{flow, AutoSetArg, AutoSetArgInQuery, AutoSetArgInQueryFilter};
+}
+
+#[doc(hidden)]
+pub mod __macro_exports {
+    pub use bevy::ecs::{
+        query::QueryData,
+        schedule::{graph::GraphInfo, Chain, Schedulable, ScheduleConfigs},
+    };
 }
@@ -0,0 +1,579 @@
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter, Write},
+};
+
+use bevy::{
+    ecs::schedule::{
+        graph::DiGraph, LogLevel, NodeId, Schedule, ScheduleBuildError, ScheduleBuildPass,
+        ScheduleGraph, Schedules,
+    },
+    log::warn,
+    prelude::World,
+};
+use disqualified::ShortName;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    access::{accessed_components, build_only_pass, component_targets},
+    flow_graph::{event_resources, ordering_graph, reachable, target_name, FlowGraph, OrderNode},
+    manifest::FlowConstraint,
+    markers::{AnyFlowSet, FlowAccess, FlowTarget},
+    serialize::SerializedWriters,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Schedule build pass reporting pairs of systems that share a [`Writes`][crate::markers::Writes]
+/// or [`WritesDyn`][crate::markers::WritesDyn] set, but run in arbitrary order relative to each
+/// other.
+///
+/// Constraints like [`write_before_read`][crate::ordering::write_before_read] order writers
+/// against readers only, so writers of the same type need to be ordered manually, for example
+/// with `.chain()` or `.before()`, or serialized with
+/// [`serialize_writers`][crate::serialize::SerializeWritersAppExt::serialize_writers].
+///
+/// Only ordering configured on the schedule is taken into account, not edges added by other build
+/// passes, so the result doesn't depend on the order passes run in. Flow inferred from access by
+/// [`AccessFlowPass`][crate::access::AccessFlowPass] counts like declared flow, but writers
+/// ordered only by flow policies are still reported.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # use bevy::ecs::schedule::LogLevel;
+/// # #[derive(Resource)]
+/// # struct Score;
+/// # let mut app = App::new();
+/// app.edit_schedule(Update, |schedule| {
+///     schedule.add_build_pass(UnorderedWritersPass::new(LogLevel::Error).allow::<Score>());
+/// });
+/// ```
+#[derive(Debug)]
+pub struct UnorderedWritersPass {
+    level: LogLevel,
+    allowed: HashSet<FlowTarget>,
+}
+
+impl Default for UnorderedWritersPass {
+    fn default() -> Self {
+        Self::new(LogLevel::Warn)
+    }
+}
+
+impl UnorderedWritersPass {
+    /// Creates the pass reporting unordered writers with the given `level`. With
+    /// [`LogLevel::Warn`] they are logged, with [`LogLevel::Error`] building the schedule fails
+    /// with a [`ScheduleBuildError::Ambiguity`] listing them, like Bevy's own ambiguity detection.
+    #[must_use]
+    pub fn new(level: LogLevel) -> Self {
+        Self {
+            level,
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allows writers of `T` to run in any order.
+    #[must_use]
+    pub fn allow<T: 'static>(self) -> Self {
+        self.allow_dyn(TypeId::of::<T>())
+    }
+
+    /// Allows writers of `target` to run in any order.
+    #[must_use]
+    pub fn allow_dyn(mut self, target: impl Into<FlowTarget>) -> Self {
+        self.allowed.insert(target.into());
+        self
+    }
+}
+
+impl ScheduleBuildPass for UnorderedWritersPass {
+    build_only_pass!();
+
+    fn build(
+        &mut self,
+        world: &mut World,
+        graph: &mut ScheduleGraph,
+        _dependency_flattened: &mut DiGraph,
+    ) -> Result<(), ScheduleBuildError> {
+        if self.level == LogLevel::Ignore {
+            return Ok(());
+        }
+
+        let mut report = String::new();
+        for (target, first, second) in unordered_writers(world, graph, &self.allowed) {
+            let _ = writeln!(
+                report,
+                " -- `{}` and `{}` both write `{}`, but are not ordered",
+                ShortName(&graph.system_at(first).name()),
+                ShortName(&graph.system_at(second).name()),
+                ShortName(&target)
+            );
+        }
+        if report.is_empty() {
+            return Ok(());
+        }
+
+        if self.level == LogLevel::Error {
+            return Err(ScheduleBuildError::Ambiguity(report));
+        }
+        warn!("Systems writing the same data have indeterminate run order.\n{report}");
+        Ok(())
+    }
+}
+
+/// Schedule build pass comparing the component and resource access of each system with the flow
+/// sets it belongs to.
+///
+/// It reports access that is not declared, like a system with `ResMut<Foo>` added without
+/// `.in_auto_sets()` and thus missing from `Writes<Foo>`, as well as manual declarations like
+/// `.reads::<Foo>()` that the system never uses. Flow sets inferred by
+/// [`.in_auto_sets()`][crate::automagic::InferFlow::in_auto_sets] are not reported as unused, as
+/// filters like `With<T>` put systems in `Reads<T>` without any access. Changes done through
+/// `Commands` are not visible in the access of a system, so they can't be checked.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # use bevy::ecs::schedule::LogLevel;
+/// # let mut app = App::new();
+/// app.edit_schedule(Update, |schedule| {
+///     schedule.add_build_pass(AccessCoveragePass::new(LogLevel::Error).allow::<Time>());
+/// });
+/// ```
+#[derive(Debug)]
+pub struct AccessCoveragePass {
+    level: LogLevel,
+    allowed: HashSet<FlowTarget>,
+}
+
+impl Default for AccessCoveragePass {
+    fn default() -> Self {
+        Self::new(LogLevel::Warn)
+    }
+}
+
+impl AccessCoveragePass {
+    /// Creates the pass reporting mismatches with the given `level`. With [`LogLevel::Warn`] they
+    /// are logged, with [`LogLevel::Error`] building the schedule fails with a
+    /// [`ScheduleBuildError::Ambiguity`], since systems missing from flow sets run in
+    /// indeterminate order relative to the flow.
+    #[must_use]
+    pub fn new(level: LogLevel) -> Self {
+        Self {
+            level,
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allows access to `T` without declaring it, and declarations of `T` without access.
+    #[must_use]
+    pub fn allow<T: 'static>(self) -> Self {
+        self.allow_dyn(TypeId::of::<T>())
+    }
+
+    /// Allows access to `target` without declaring it, and declarations of `target` without
+    /// access.
+    #[must_use]
+    pub fn allow_dyn(mut self, target: impl Into<FlowTarget>) -> Self {
+        self.allowed.insert(target.into());
+        self
+    }
+}
+
+impl ScheduleBuildPass for AccessCoveragePass {
+    build_only_pass!();
+
+    fn build(
+        &mut self,
+        world: &mut World,
+        graph: &mut ScheduleGraph,
+        _dependency_flattened: &mut DiGraph,
+    ) -> Result<(), ScheduleBuildError> {
+        if self.level == LogLevel::Ignore {
+            return Ok(());
+        }
+
+        let report = access_coverage(world, graph, &self.allowed);
+        if report.is_empty() {
+            return Ok(());
+        }
+
+        let message = format!("Flow sets of systems don't match their access.\n{report}");
+        if self.level == LogLevel::Error {
+            return Err(ScheduleBuildError::Ambiguity(message));
+        }
+        warn!("{message}");
+        Ok(())
+    }
+}
+
+/// Returns a line for each access that is not declared and each manual declaration that is not
+/// used.
+fn access_coverage(world: &World, graph: &ScheduleGraph, allowed: &HashSet<FlowTarget>) -> String {
+    let flow = FlowGraph::extract_in(world, graph);
+    let event_targets = event_resources(graph, world.components());
+    let verb = |access| match access {
+        FlowAccess::Read => "reads",
+        FlowAccess::Write => "writes",
+        FlowAccess::Modify => "modifies",
+    };
+
+    let mut report = String::new();
+    for (id, system, _) in graph.systems() {
+        let name = system.name();
+        let name = ShortName(&name);
+        let declared = flow
+            .memberships
+            .iter()
+            .filter(|&&(member, _, _)| member == id)
+            .filter_map(|&(_, set, inferred)| {
+                flow.set(set)
+                    .map(|(_, kind, label)| (*kind, label, inferred))
+            })
+            .collect::<Vec<_>>();
+
+        let access = system.component_access();
+        let all = if system.is_exclusive() || access.has_write_all() {
+            Some(FlowAccess::Write)
+        } else if access.has_read_all() {
+            Some(FlowAccess::Read)
+        } else {
+            None
+        };
+        let actual = accessed_components(access)
+            .into_iter()
+            .map(|(component, access)| {
+                let targets = component_targets(world, component, &event_targets);
+                let component = world
+                    .components()
+                    .get_name(component)
+                    .map(|name| ShortName(&name).to_string())
+                    .unwrap_or_default();
+                (access, targets, component)
+            })
+            .collect::<Vec<_>>();
+
+        for (access, targets, component) in &actual {
+            if targets.iter().any(|target| allowed.contains(target)) {
+                continue;
+            }
+            if !declared
+                .iter()
+                .any(|(kind, _, _)| covers(kind, *access, targets))
+            {
+                let _ = writeln!(
+                    report,
+                    " -- `{name}` {} `{component}`, but is not in a flow set for it",
+                    verb(*access)
+                );
+            }
+        }
+        if let Some(access) = all {
+            let covered = declared.iter().any(
+                |(kind, _, _)| matches!(kind, AnyFlowSet::All(all) if *all == FlowAccess::Write || *all == access),
+            );
+            if !covered {
+                let _ = writeln!(
+                    report,
+                    " -- `{name}` {} all data, but is not in `ReadsAll` or `WritesAll`",
+                    verb(access)
+                );
+            }
+        }
+
+        for (kind, label, inferred) in &declared {
+            let AnyFlowSet::Flow(set) = kind else {
+                continue;
+            };
+            if *inferred || allowed.contains(&set.target) {
+                continue;
+            }
+            let used = all
+                .is_some_and(|all| all == FlowAccess::Write || set.access == FlowAccess::Read)
+                || actual.iter().any(|(access, targets, _)| {
+                    targets.contains(&set.target)
+                        && (set.access == FlowAccess::Read || *access == FlowAccess::Write)
+                });
+            if !used {
+                let _ = writeln!(
+                    report,
+                    " -- `{name}` is in `{label}`, but never {} it",
+                    verb(set.access)
+                );
+            }
+        }
+    }
+    report
+}
+
+/// Returns `true` if a system in `set` may access `targets` in the given way.
+fn covers(set: &AnyFlowSet, access: FlowAccess, targets: &[FlowTarget]) -> bool {
+    match set {
+        AnyFlowSet::All(all) => *all == FlowAccess::Write || access == FlowAccess::Read,
+        AnyFlowSet::Flow(set) => {
+            targets.contains(&set.target) && (access == FlowAccess::Read || set.access.writes())
+        }
+    }
+}
+
+/// Returns pairs of systems writing or modifying the same data with no ordering path between them
+/// in the configured ordering, together with the name of the written data. Serialized writers are
+/// skipped.
+fn unordered_writers(
+    world: &World,
+    graph: &ScheduleGraph,
+    allowed: &HashSet<FlowTarget>,
+) -> Vec<(String, NodeId, NodeId)> {
+    let flow_graph = FlowGraph::extract_in(world, graph);
+    let serialized = graph
+        .system_sets()
+        .filter_map(|(_, set, _)| set.as_dyn_eq().as_any().downcast_ref::<SerializedWriters>())
+        .map(SerializedWriters::target)
+        .collect::<HashSet<_>>();
+    let mut targets = Vec::<(FlowTarget, String, Vec<NodeId>)>::new();
+    for (id, set, _) in graph.system_sets() {
+        let Some(kind @ AnyFlowSet::Flow(flow)) = AnyFlowSet::from_set(set) else {
+            continue;
+        };
+        if !flow.access.writes()
+            || allowed.contains(&flow.target)
+            || serialized.contains(&flow.target)
+        {
+            continue;
+        }
+        let position = targets
+            .iter()
+            .position(|(target, _, _)| *target == flow.target);
+        let index = position.unwrap_or_else(|| {
+            targets.push((flow.target, target_name(&kind), Vec::new()));
+            targets.len() - 1
+        });
+        let (_, name, writers) = &mut targets[index];
+        if flow.name.is_some() {
+            *name = target_name(&kind);
+        }
+        for system in flow_graph.members(id) {
+            if !writers.contains(&system) {
+                writers.push(system);
+            }
+        }
+    }
+
+    let edges = ordering_graph(graph, &flow_graph);
+    let mut successors = HashMap::<NodeId, HashSet<OrderNode>>::new();
+    let mut pairs = Vec::new();
+    for (_, name, writers) in targets {
+        for (index, &first) in writers.iter().enumerate() {
+            for &second in &writers[index + 1..] {
+                let ordered = [(first, second), (second, first)]
+                    .into_iter()
+                    .any(|(from, to)| {
+                        successors
+                            .entry(from)
+                            .or_insert_with(|| reachable(&edges, OrderNode::System(from)))
+                            .contains(&OrderNode::System(to))
+                    });
+                if !ordered {
+                    pairs.push((name.clone(), first.min(second), first.max(second)));
+                }
+            }
+        }
+    }
+    pairs
+}
+
+/// Report of data flow that leads nowhere: data that is read but never written, data that is
+/// written but never read, and constraints ordering flow sets without any systems. Data whose
+/// readers all modify it counts as never read.
+///
+/// Only [`Reads`][crate::markers::Reads], [`Writes`][crate::markers::Writes] and their dynamic
+/// counterparts are taken into account, so systems in [`ReadsAll`][crate::markers::ReadsAll] or
+/// [`WritesAll`][crate::markers::WritesAll] count as readers or writers only of types whose
+/// constraints nest these sets, like [`write_before_read`][crate::ordering::write_before_read].
+/// Flow inferred from access by [`AccessFlowPass`][crate::access::AccessFlowPass] and
+/// [`FlowSortingPass`][crate::access::FlowSortingPass] is seen once the schedule is built.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # let app = App::new();
+/// let report = DeadFlowReport::from_schedules(app.world().resource::<Schedules>());
+/// if !report.is_empty() {
+///     warn!("{report}");
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeadFlowReport {
+    /// Types that are read, but never written, sorted by their full names.
+    pub only_read: Vec<String>,
+    /// Types that are written, but never read, sorted by their full names.
+    pub only_written: Vec<String>,
+    /// Constraints with a flow set without any systems on at least one side.
+    pub empty_constraints: Vec<EmptyConstraint>,
+}
+
+/// Constraint ordering a flow set without any systems, reported by [`DeadFlowReport`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct EmptyConstraint {
+    /// Label of the schedule the constraint is configured in.
+    pub schedule: String,
+    /// The constraint.
+    pub constraint: FlowConstraint,
+    /// Labels of the sets without any systems.
+    pub empty_sets: Vec<String>,
+}
+
+/// Whether a flow target is read or written anywhere, with its best known name.
+#[derive(Default)]
+struct TargetUse {
+    name: String,
+    read: bool,
+    written: bool,
+}
+
+impl DeadFlowReport {
+    /// Creates the report for data flow within `schedule`.
+    #[must_use]
+    pub fn from_schedule(schedule: &Schedule) -> Self {
+        Self::collect([schedule])
+    }
+
+    /// Creates the report for data flow across all `schedules`, so data written in one schedule
+    /// and read in another is not reported.
+    #[must_use]
+    pub fn from_schedules(schedules: &Schedules) -> Self {
+        Self::collect(schedules.iter().map(|(_, schedule)| schedule))
+    }
+
+    /// Returns `true` if nothing was reported.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.only_read.is_empty()
+            && self.only_written.is_empty()
+            && self.empty_constraints.is_empty()
+    }
+
+    fn collect<'a>(schedules: impl IntoIterator<Item = &'a Schedule>) -> Self {
+        let mut targets = HashMap::<FlowTarget, TargetUse>::new();
+        let mut empty_constraints = Vec::new();
+        for schedule in schedules {
+            let flow = FlowGraph::from_schedule(schedule);
+            let is_used = |set: NodeId| {
+                flow.memberships
+                    .iter()
+                    .any(|&(_, member_of, _)| member_of == set)
+            };
+
+            let mut members = HashMap::<(FlowTarget, FlowAccess), HashSet<NodeId>>::new();
+            for &(system, member_of, _) in &flow.memberships {
+                if let Some((_, AnyFlowSet::Flow(set), _)) = flow.set(member_of) {
+                    members
+                        .entry((set.target, set.access))
+                        .or_default()
+                        .insert(system);
+                }
+            }
+            for (id, kind, _) in &flow.sets {
+                let AnyFlowSet::Flow(set) = kind else {
+                    continue;
+                };
+                let target = targets.entry(set.target).or_default();
+                if target.name.is_empty() || set.name.is_some() {
+                    target.name = target_name(kind);
+                }
+                if !is_used(*id) {
+                    continue;
+                }
+                if set.access.writes() {
+                    target.written = true;
+                } else {
+                    // Modifiers read the data only to write it back, so they don't count as
+                    // readers.
+                    let modifiers = members.get(&(set.target, FlowAccess::Modify));
+                    target.read |= members[&(set.target, FlowAccess::Read)]
+                        .iter()
+                        .any(|system| {
+                            modifiers.is_none_or(|modifiers| !modifiers.contains(system))
+                        });
+                }
+            }
+
+            // Constraints like `write_before_read` order both `Writes` and `Modifies` sets, so a
+            // set on the writing side only counts as empty if the other one is empty too.
+            let is_empty = |node: NodeId| match flow.set(node) {
+                Some((_, AnyFlowSet::Flow(set), _)) if set.access.writes() => {
+                    !flow.sets.iter().any(|(id, kind, _)| {
+                        matches!(kind, AnyFlowSet::Flow(other)
+                            if other.target == set.target && other.access.writes())
+                            && is_used(*id)
+                    })
+                }
+                Some(_) => !is_used(node),
+                None => false,
+            };
+
+            for &(before, after) in &flow.constraints {
+                let empty_sets = [before, after]
+                    .into_iter()
+                    .filter(|&node| is_empty(node))
+                    .map(|node| flow.node_name(node))
+                    .collect::<Vec<_>>();
+                if !empty_sets.is_empty() {
+                    empty_constraints.push(EmptyConstraint {
+                        schedule: format!("{:?}", schedule.label()),
+                        constraint: FlowConstraint {
+                            before: flow.node_name(before),
+                            after: flow.node_name(after),
+                        },
+                        empty_sets,
+                    });
+                }
+            }
+        }
+
+        let names = |filter: fn(&TargetUse) -> bool| {
+            let mut names = targets
+                .values()
+                .filter(|target| filter(target))
+                .map(|target| target.name.clone())
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+        empty_constraints
+            .sort_by(|a, b| (&a.schedule, &a.constraint).cmp(&(&b.schedule, &b.constraint)));
+        Self {
+            only_read: names(|target| target.read && !target.written),
+            only_written: names(|target| target.written && !target.read),
+            empty_constraints,
+        }
+    }
+}
+
+impl Display for DeadFlowReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for name in &self.only_read {
+            writeln!(f, " -- `{}` is read, but never written", ShortName(name))?;
+        }
+        for name in &self.only_written {
+            writeln!(f, " -- `{}` is written, but never read", ShortName(name))?;
+        }
+        for empty in &self.empty_constraints {
+            let sets = empty
+                .empty_sets
+                .iter()
+                .map(|set| format!("`{set}`"))
+                .collect::<Vec<_>>()
+                .join(" and ");
+            writeln!(
+                f,
+                " -- `{} before {}` in `{}` orders {sets} without any systems",
+                empty.constraint.before, empty.constraint.after, empty.schedule
+            )?;
+        }
+        Ok(())
+    }
+}
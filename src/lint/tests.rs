@@ -0,0 +1,266 @@
+use std::any::{type_name, TypeId};
+
+use bevy::{
+    ecs::schedule::{
+        graph::DiGraph, LogLevel, ScheduleBuildError, ScheduleBuildPass, ScheduleGraph, Schedules,
+    },
+    prelude::*,
+};
+
+use super::{AccessCoveragePass, DeadFlowReport, UnorderedWritersPass};
+use crate::{
+    access::build_only_pass,
+    prelude::{
+        write_before_read, InferFlow, IntoSystemRW, SerializeWritersPass, SerializedWriters,
+    },
+};
+
+#[test]
+fn unordered_writers_are_reported() {
+    let result = build(
+        UnorderedWritersPass::new(LogLevel::Error),
+        (first_writer.in_auto_sets(), second_writer.in_auto_sets()),
+    );
+
+    let Err(ScheduleBuildError::Ambiguity(report)) = result else {
+        panic!("expected an ambiguity, got {result:?}");
+    };
+    assert_eq!(
+        report,
+        " -- `first_writer` and `second_writer` both write `Score`, but are not ordered\n"
+    );
+}
+
+#[test]
+fn ordered_writers_are_accepted() {
+    let result = build(
+        UnorderedWritersPass::new(LogLevel::Error),
+        (first_writer.in_auto_sets(), second_writer.in_auto_sets()).chain(),
+    );
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn serialized_writers_are_accepted() {
+    let mut world = World::new();
+    let mut schedule = Schedule::new(Update);
+    schedule
+        .add_build_pass(UnorderedWritersPass::new(LogLevel::Error))
+        .add_build_pass(SerializeWritersPass)
+        .add_systems((first_writer.in_auto_sets(), second_writer.in_auto_sets()))
+        .configure_sets(SerializedWriters::new(TypeId::of::<Score>()));
+
+    assert!(schedule.initialize(&mut world).is_ok());
+}
+
+#[test]
+fn edges_of_other_passes_are_ignored() {
+    let mut world = World::new();
+    let mut schedule = Schedule::new(Update);
+    schedule
+        .add_build_pass(UnorderedWritersPass::new(LogLevel::Error))
+        .add_build_pass(OrderAllSystems)
+        .add_systems((first_writer.in_auto_sets(), second_writer.in_auto_sets()));
+
+    let result = schedule.initialize(&mut world);
+    assert!(matches!(result, Err(ScheduleBuildError::Ambiguity(_))));
+}
+
+#[test]
+fn allowed_types_are_skipped() {
+    let result = build(
+        UnorderedWritersPass::new(LogLevel::Error).allow::<Score>(),
+        (first_writer.in_auto_sets(), second_writer.in_auto_sets()),
+    );
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn warnings_do_not_fail_the_build() {
+    for level in [LogLevel::Warn, LogLevel::Ignore] {
+        let result = build(
+            UnorderedWritersPass::new(level),
+            (first_writer.in_auto_sets(), second_writer.in_auto_sets()),
+        );
+
+        assert!(result.is_ok());
+    }
+}
+
+#[test]
+fn dead_flow_is_reported() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (
+            first_writer.in_auto_sets(),
+            score_reader.in_auto_sets(),
+            alarm_listener.in_auto_sets(),
+            log_writer.in_auto_sets(),
+        ),
+    )
+    .configure_sets(Update, write_before_read::<Unused>());
+
+    let report = DeadFlowReport::from_schedule(app.get_schedule(Update).unwrap());
+
+    assert_eq!(report.only_read, vec![type_name::<Alarm>()]);
+    assert_eq!(report.only_written, vec![type_name::<Log>()]);
+    assert_eq!(report.empty_constraints.len(), 2);
+    let mut before = Vec::new();
+    for empty in &report.empty_constraints {
+        assert_eq!(empty.schedule, "Update");
+        assert_eq!(empty.constraint.after, "Reads(\"Unused\")");
+        assert_eq!(empty.empty_sets.len(), 2);
+        before.push(empty.constraint.before.as_str());
+    }
+    before.sort_unstable();
+    assert_eq!(before, vec!["Modifies(\"Unused\")", "Writes(\"Unused\")"]);
+    assert!(report
+        .to_string()
+        .contains("`Alarm` is read, but never written"));
+}
+
+#[test]
+fn flow_across_schedules_is_not_dead() {
+    let mut app = App::new();
+    app.add_systems(Update, alarm_listener.in_auto_sets())
+        .add_systems(PostUpdate, alarm_raiser.in_auto_sets())
+        .configure_sets(Update, write_before_read::<Alarm>());
+
+    let single = DeadFlowReport::from_schedule(app.get_schedule(Update).unwrap());
+    assert_eq!(single.only_read, vec![type_name::<Alarm>()]);
+    let mut empty_sets = single
+        .empty_constraints
+        .iter()
+        .flat_map(|empty| empty.empty_sets.iter().map(String::as_str))
+        .collect::<Vec<_>>();
+    empty_sets.sort_unstable();
+    assert_eq!(empty_sets, vec!["Modifies(\"Alarm\")", "Writes(\"Alarm\")"]);
+
+    let report = DeadFlowReport::from_schedules(app.world().resource::<Schedules>());
+    assert!(report.only_read.is_empty() && report.only_written.is_empty());
+}
+
+#[test]
+fn undeclared_access_is_reported() {
+    let report = build_error(
+        AccessCoveragePass::new(LogLevel::Error),
+        (first_writer, score_reader.in_auto_sets()),
+    );
+
+    assert!(report.contains("`first_writer` writes `Score`, but is not in a flow set for it"));
+    assert!(!report.contains("score_reader"));
+}
+
+#[test]
+fn unused_declarations_are_reported() {
+    let report = build_error(
+        AccessCoveragePass::new(LogLevel::Error),
+        score_reader.in_auto_sets().reads::<Log>(),
+    );
+
+    assert_eq!(
+        report,
+        "Flow sets of systems don't match their access.\n -- `score_reader` is in \
+         `Reads(\"Log\")`, but never reads it\n"
+    );
+}
+
+#[test]
+fn inferred_flow_covers_access() {
+    let result = build(
+        AccessCoveragePass::new(LogLevel::Error),
+        (
+            first_writer.in_auto_sets(),
+            alarm_listener.in_auto_sets(),
+            alarm_raiser.in_auto_sets(),
+            marked_reader.in_auto_sets(),
+        ),
+    );
+
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn allowed_access_is_skipped() {
+    let result = build(
+        AccessCoveragePass::new(LogLevel::Error).allow::<Score>(),
+        first_writer,
+    );
+
+    assert!(result.is_ok());
+}
+
+fn build<M>(
+    pass: impl ScheduleBuildPass,
+    systems: impl IntoScheduleConfigs<bevy::ecs::system::ScheduleSystem, M>,
+) -> Result<(), ScheduleBuildError> {
+    let mut world = World::new();
+    let mut schedule = Schedule::new(Update);
+    schedule.add_build_pass(pass);
+    schedule.add_systems(systems);
+    schedule.initialize(&mut world)
+}
+
+/// Builds the schedule, expecting it to fail with an ambiguity, and returns the report.
+fn build_error<M>(
+    pass: impl ScheduleBuildPass,
+    systems: impl IntoScheduleConfigs<bevy::ecs::system::ScheduleSystem, M>,
+) -> String {
+    let result = build(pass, systems);
+    let Err(ScheduleBuildError::Ambiguity(report)) = result else {
+        panic!("expected an ambiguity, got {result:?}");
+    };
+    report
+}
+
+/// Build pass ordering all systems by their index.
+#[derive(Debug)]
+struct OrderAllSystems;
+
+impl ScheduleBuildPass for OrderAllSystems {
+    build_only_pass!();
+
+    fn build(
+        &mut self,
+        _world: &mut World,
+        graph: &mut ScheduleGraph,
+        dependency_flattened: &mut DiGraph,
+    ) -> Result<(), ScheduleBuildError> {
+        let systems = graph.systems().map(|(id, _, _)| id).collect::<Vec<_>>();
+        for pair in systems.windows(2) {
+            dependency_flattened.add_edge(pair[0], pair[1]);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Resource)]
+struct Score;
+
+fn first_writer(_score: ResMut<Score>) {}
+
+fn second_writer(_score: ResMut<Score>) {}
+
+#[derive(Resource)]
+struct Log;
+
+#[derive(Event)]
+struct Alarm;
+
+struct Unused;
+
+fn score_reader(_score: Res<Score>) {}
+
+fn log_writer(_log: ResMut<Log>) {}
+
+fn alarm_listener(_alarms: EventReader<Alarm>) {}
+
+fn alarm_raiser(_alarms: EventWriter<Alarm>) {}
+
+#[derive(Component)]
+struct Marker;
+
+fn marked_reader(_query: Query<Entity, With<Marker>>) {}
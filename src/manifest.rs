@@ -0,0 +1,158 @@
+use bevy::ecs::schedule::{Schedule, Schedules};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    flow_graph::{target_name, FlowGraph},
+    markers::FlowAccess,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Serializable description of the data flow in schedules: which systems read and write what, and
+/// which constraints are configured.
+///
+/// It is meant to be stored next to the code and diffed in code review, or consumed by external
+/// tools.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let app = App::new();
+/// let manifest = FlowManifest::from_schedules(app.world().resource::<Schedules>());
+/// std::fs::write("flow.json", serde_json::to_string_pretty(&manifest)?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct FlowManifest {
+    /// Data flow of each schedule, sorted by the schedule label.
+    pub schedules: Vec<ScheduleFlow>,
+}
+
+/// Data flow of a single schedule.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScheduleFlow {
+    /// Label of the schedule.
+    pub schedule: String,
+    /// All systems of the schedule, sorted by name.
+    pub systems: Vec<SystemFlow>,
+    /// All constraints with auto-sets on at least one side, sorted.
+    pub constraints: Vec<FlowConstraint>,
+}
+
+/// Data flow of a single system. Types are listed by their full names, with `*` standing for
+/// [`ReadsAll`][crate::markers::ReadsAll] and [`WritesAll`][crate::markers::WritesAll].
+///
+/// Flow is reported as inferred whenever `.in_auto_sets()` inferred it for the system in this
+/// schedule, even if it was also specified manually.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct SystemFlow {
+    /// Name of the system.
+    pub name: String,
+    /// Types read by the system, inferred by [`.in_auto_sets()`][crate::automagic::InferFlow::in_auto_sets].
+    pub inferred_reads: Vec<String>,
+    /// Types written by the system, inferred by [`.in_auto_sets()`][crate::automagic::InferFlow::in_auto_sets].
+    pub inferred_writes: Vec<String>,
+    /// Types modified by the system, inferred by [`.in_auto_sets()`][crate::automagic::InferFlow::in_auto_sets].
+    #[serde(default)]
+    pub inferred_modifies: Vec<String>,
+    /// Types read by the system, specified manually.
+    pub manual_reads: Vec<String>,
+    /// Types written by the system, specified manually.
+    pub manual_writes: Vec<String>,
+    /// Types modified by the system, specified manually.
+    #[serde(default)]
+    pub manual_modifies: Vec<String>,
+}
+
+/// Ordering between two auto-sets or systems, like the one added by
+/// [`write_before_read`][crate::ordering::write_before_read].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FlowConstraint {
+    /// Set or system that runs first.
+    pub before: String,
+    /// Set or system that runs later.
+    pub after: String,
+}
+
+impl FlowManifest {
+    /// Creates the manifest describing all `schedules`.
+    #[must_use]
+    pub fn from_schedules(schedules: &Schedules) -> Self {
+        let mut schedules = schedules
+            .iter()
+            .map(|(_, schedule)| ScheduleFlow::from_schedule(schedule))
+            .collect::<Vec<_>>();
+        schedules.sort_by(|a, b| a.schedule.cmp(&b.schedule));
+        Self { schedules }
+    }
+}
+
+impl ScheduleFlow {
+    /// Creates the description of `schedule`.
+    #[must_use]
+    pub fn from_schedule(schedule: &Schedule) -> Self {
+        let flow = FlowGraph::from_schedule(schedule);
+
+        let mut systems = flow
+            .systems
+            .iter()
+            .map(|(id, name)| {
+                let mut system = SystemFlow {
+                    name: name.clone(),
+                    ..SystemFlow::default()
+                };
+                let sets = flow
+                    .memberships
+                    .iter()
+                    .filter(|(member, _, _)| member == id);
+                for &(_, set, inferred) in sets {
+                    let Some((_, kind, _)) = flow.set(set) else {
+                        continue;
+                    };
+                    let list = match (kind.access(), inferred) {
+                        (FlowAccess::Read, true) => &mut system.inferred_reads,
+                        (FlowAccess::Write, true) => &mut system.inferred_writes,
+                        (FlowAccess::Modify, true) => &mut system.inferred_modifies,
+                        (FlowAccess::Read, false) => &mut system.manual_reads,
+                        (FlowAccess::Write, false) => &mut system.manual_writes,
+                        (FlowAccess::Modify, false) => &mut system.manual_modifies,
+                    };
+                    list.push(target_name(kind));
+                }
+                for list in [
+                    &mut system.inferred_reads,
+                    &mut system.inferred_writes,
+                    &mut system.inferred_modifies,
+                    &mut system.manual_reads,
+                    &mut system.manual_writes,
+                    &mut system.manual_modifies,
+                ] {
+                    list.sort();
+                    list.dedup();
+                }
+                system
+            })
+            .collect::<Vec<_>>();
+        systems.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut constraints = flow
+            .constraints
+            .iter()
+            .map(|&(before, after)| FlowConstraint {
+                before: flow.node_name(before),
+                after: flow.node_name(after),
+            })
+            .collect::<Vec<_>>();
+        constraints.sort();
+        constraints.dedup();
+
+        Self {
+            schedule: format!("{:?}", schedule.label()),
+            systems,
+            constraints,
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::any::type_name;
+
+use bevy::{ecs::schedule::Schedules, prelude::*};
+
+use super::{FlowConstraint, FlowManifest, ScheduleFlow, SystemFlow};
+use crate::prelude::{write_before_read, InferFlow, IntoSystemRW};
+
+#[test]
+fn manifest_separates_inferred_and_manual_flow() {
+    let mut app = App::new();
+    app.add_systems(
+        Update,
+        (
+            score_writer.in_auto_sets().writes::<Marker>(),
+            score_reader.reads::<Score>(),
+        ),
+    )
+    .configure_sets(Update, write_before_read::<Score>());
+
+    let flow = ScheduleFlow::from_schedule(app.get_schedule(Update).unwrap());
+
+    assert_eq!(flow.schedule, "Update");
+    assert_eq!(flow.systems.len(), 2);
+    let writer = find(&flow, "score_writer");
+    assert_eq!(writer.inferred_modifies, vec![type_name::<Score>()]);
+    assert_eq!(writer.manual_writes, vec![type_name::<Marker>()]);
+    assert!(writer.inferred_writes.is_empty() && writer.manual_modifies.is_empty());
+    assert!(writer.inferred_reads.is_empty() && writer.manual_reads.is_empty());
+
+    let reader = find(&flow, "score_reader");
+    assert_eq!(reader.manual_reads, vec![type_name::<Score>()]);
+    assert!(reader.inferred_reads.is_empty() && reader.inferred_writes.is_empty());
+
+    assert_eq!(
+        flow.constraints,
+        vec![
+            FlowConstraint {
+                before: "Modifies(\"Score\")".to_owned(),
+                after: "Reads(\"Score\")".to_owned(),
+            },
+            FlowConstraint {
+                before: "Writes(\"Score\")".to_owned(),
+                after: "Reads(\"Score\")".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn membership_through_inferred_nested_set_is_inferred() {
+    let mut app = App::new();
+    app.add_systems(Update, world_writer.in_auto_sets())
+        .configure_sets(Update, write_before_read::<Score>());
+
+    let flow = ScheduleFlow::from_schedule(app.get_schedule(Update).unwrap());
+    let writer = find(&flow, "world_writer");
+    assert!(writer
+        .inferred_writes
+        .iter()
+        .any(|name| name == type_name::<Score>()));
+    assert!(writer.manual_writes.is_empty() && writer.manual_modifies.is_empty());
+}
+
+#[test]
+fn manifest_round_trips_through_json() {
+    let mut app = App::new();
+    app.add_systems(Update, score_writer.in_auto_sets())
+        .add_systems(PostUpdate, score_observer.in_auto_sets())
+        .configure_sets(Update, write_before_read::<Score>());
+
+    let manifest = FlowManifest::from_schedules(app.world().resource::<Schedules>());
+    let labels = manifest
+        .schedules
+        .iter()
+        .map(|schedule| schedule.schedule.as_str())
+        .collect::<Vec<_>>();
+    assert!(labels.contains(&"Update") && labels.contains(&"PostUpdate"));
+    assert!(labels.is_sorted());
+
+    let json = serde_json::to_string(&manifest).unwrap();
+    let parsed: FlowManifest = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, manifest);
+}
+
+fn find<'a>(flow: &'a ScheduleFlow, name: &str) -> &'a SystemFlow {
+    flow.systems
+        .iter()
+        .find(|system| system.name.ends_with(name))
+        .unwrap()
+}
+
+#[derive(Resource)]
+struct Score;
+
+struct Marker;
+
+fn score_writer(_score: ResMut<Score>) {}
+
+fn score_reader(_score: Res<Score>) {}
+
+fn score_observer(_score: Res<Score>) {}
+
+fn world_writer(_world: &mut World) {}
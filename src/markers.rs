@@ -1,5 +1,5 @@
 use std::{
-    any::TypeId,
+    any::{self, TypeId},
     fmt::{self, Debug, Formatter},
     hash::{Hash, Hasher},
     marker::PhantomData,
@@ -7,19 +7,163 @@ use std::{
 
 use bevy::{
     ecs::{
+        component::{ComponentId, ComponentInfo, Components},
         label,
         schedule::{graph::GraphInfo, Chain, Schedulable, ScheduleConfigs},
+        system::{ExclusiveSystemParamFunction, ScheduleSystem},
     },
-    prelude::{IntoScheduleConfigs, SystemSet},
+    prelude::{Event, Events, IntoScheduleConfigs, SystemSet},
 };
+use bevy_utils_proc_macros::all_tuples;
+use disqualified::ShortName;
 use tynm::type_name;
 
+use crate::{flow_graph::note_flow_set, serialize::WritePriority};
+
+/// Data that a dynamic flow set is created for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FlowTarget {
+    /// Rust type, like the ones used in [`Reads`], [`Writes`] and [`Modifies`].
+    Type(TypeId),
+    /// Component or resource without a corresponding rust type, like the ones registered with
+    /// `register_component_with_descriptor`. Use [`FlowTarget::component`] to create targets for
+    /// a [`ComponentId`].
+    Component(ComponentId),
+}
+
+impl FlowTarget {
+    /// Returns the target for the component or resource `id`. It is the rust type of the
+    /// component if it has one, so sets created for it are the same sets as the typed ones.
+    ///
+    /// ```rust
+    /// # use std::any::TypeId;
+    /// # use bevy::prelude::*;
+    /// # use bevy_sorting::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Health;
+    /// # let mut world = World::new();
+    /// let id = world.register_component::<Health>();
+    /// let target = FlowTarget::component(world.components(), id);
+    /// assert_eq!(target, FlowTarget::Type(TypeId::of::<Health>()));
+    /// ```
+    #[must_use]
+    pub fn component(components: &Components, id: ComponentId) -> Self {
+        components
+            .get_info(id)
+            .and_then(ComponentInfo::type_id)
+            .map_or(Self::Component(id), Self::Type)
+    }
+}
+
+impl From<TypeId> for FlowTarget {
+    fn from(type_id: TypeId) -> Self {
+        Self::Type(type_id)
+    }
+}
+
+/// Identity of a [`Reads`], [`Writes`] or [`Modifies`] set, shared by all instances with the same
+/// target.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct FlowSet {
+    pub(crate) access: FlowAccess,
+    pub(crate) target: FlowTarget,
+    /// Full name of the target type, if known. It is not a part of the identity.
+    pub(crate) name: Option<&'static str>,
+}
+
+/// Kind of access described by a flow set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FlowAccess {
+    /// [`Reads`], [`ReadsDyn`] or [`ReadsAll`] set.
+    Read,
+    /// [`Writes`], [`WritesDyn`] or [`WritesAll`] set.
+    Write,
+    /// [`Modifies`] or [`ModifiesDyn`] set.
+    Modify,
+}
+
+impl FlowAccess {
+    /// Returns `true` for sets of systems that may change the data: [`Write`][Self::Write] and
+    /// [`Modify`][Self::Modify].
+    #[must_use]
+    pub fn writes(self) -> bool {
+        matches!(self, Self::Write | Self::Modify)
+    }
+}
+
+impl FlowSet {
+    fn of<T: 'static>(access: FlowAccess) -> Self {
+        Self {
+            access,
+            target: FlowTarget::Type(TypeId::of::<T>()),
+            name: Some(any::type_name::<T>()),
+        }
+    }
+
+    pub(crate) fn dynamic(access: FlowAccess, target: FlowTarget) -> Self {
+        Self {
+            access,
+            target,
+            name: None,
+        }
+    }
+
+    /// Returns the identity of `set` if it is one of [`Reads`], [`Writes`], [`Modifies`],
+    /// [`ReadsDyn`], [`WritesDyn`] or [`ModifiesDyn`] sets.
+    pub(crate) fn from_set(set: &dyn SystemSet) -> Option<Self> {
+        set.as_dyn_eq().as_any().downcast_ref::<Self>().copied()
+    }
+}
+
+impl PartialEq for FlowSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.access == other.access && self.target == other.target
+    }
+}
+
+impl Eq for FlowSet {}
+
+impl Hash for FlowSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.access.hash(state);
+        self.target.hash(state);
+    }
+}
+
+/// Any set describing data flow: one of the [`Reads`], [`Writes`], [`Modifies`], [`ReadsDyn`],
+/// [`WritesDyn`], [`ModifiesDyn`], [`ReadsAll`] and [`WritesAll`] sets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum AnyFlowSet {
+    Flow(FlowSet),
+    All(FlowAccess),
+}
+
+impl AnyFlowSet {
+    pub(crate) fn from_set(set: &dyn SystemSet) -> Option<Self> {
+        let any = set.as_dyn_eq().as_any();
+        if any.is::<ReadsAll>() {
+            Some(Self::All(FlowAccess::Read))
+        } else if any.is::<WritesAll>() {
+            Some(Self::All(FlowAccess::Write))
+        } else {
+            FlowSet::from_set(set).map(Self::Flow)
+        }
+    }
+
+    pub(crate) fn access(&self) -> FlowAccess {
+        match self {
+            Self::Flow(flow) => flow.access,
+            Self::All(access) => *access,
+        }
+    }
+}
+
 /// System set marking all systems that reads value of T
-pub struct Reads<T: 'static>(PhantomData<fn() -> T>);
+pub struct Reads<T: 'static>(FlowSet, PhantomData<fn() -> T>);
 
 impl<T: 'static> Default for Reads<T> {
     fn default() -> Self {
-        Self(PhantomData)
+        Self(FlowSet::of::<T>(FlowAccess::Read), PhantomData)
     }
 }
 
@@ -30,7 +174,9 @@ impl<T> Debug for Reads<T> {
 }
 
 impl<T> Hash for Reads<T> {
-    fn hash<H: Hasher>(&self, _state: &mut H) {}
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
 }
 
 impl<T> Clone for Reads<T> {
@@ -42,8 +188,8 @@ impl<T> Clone for Reads<T> {
 impl<T> Copy for Reads<T> {}
 
 impl<T> PartialEq for Reads<T> {
-    fn eq(&self, _other: &Self) -> bool {
-        true
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
     }
 }
 
@@ -55,20 +201,21 @@ impl<T> SystemSet for Reads<T> {
     }
 
     fn as_dyn_eq(&self) -> &dyn label::DynEq {
-        self
+        &self.0
     }
 
     fn dyn_hash(&self, mut state: &mut dyn Hasher) {
-        TypeId::of::<Self>().hash(&mut state);
+        TypeId::of::<FlowSet>().hash(&mut state);
+        self.0.hash(&mut state);
     }
 }
 
 /// System set for all systems that writes to T
-pub struct Writes<T: 'static>(PhantomData<fn() -> T>);
+pub struct Writes<T: 'static>(FlowSet, PhantomData<fn() -> T>);
 
 impl<T: 'static> Default for Writes<T> {
     fn default() -> Self {
-        Self(PhantomData)
+        Self(FlowSet::of::<T>(FlowAccess::Write), PhantomData)
     }
 }
 
@@ -79,7 +226,9 @@ impl<T> Debug for Writes<T> {
 }
 
 impl<T> Hash for Writes<T> {
-    fn hash<H: Hasher>(&self, _state: &mut H) {}
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
 }
 
 impl<T> Clone for Writes<T> {
@@ -91,8 +240,8 @@ impl<T> Clone for Writes<T> {
 impl<T> Copy for Writes<T> {}
 
 impl<T> PartialEq for Writes<T> {
-    fn eq(&self, _other: &Self) -> bool {
-        true
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
     }
 }
 
@@ -104,11 +253,246 @@ impl<T> SystemSet for Writes<T> {
     }
 
     fn as_dyn_eq(&self) -> &dyn label::DynEq {
-        self
+        &self.0
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<FlowSet>().hash(&mut state);
+        self.0.hash(&mut state);
+    }
+}
+
+/// System set for all systems that read T and write a new value based on it, like systems taking
+/// `ResMut<T>` or `Query<&mut T>`
+///
+/// Use it to build pipelines where systems only producing a value run first, then systems
+/// modifying it, then systems reading it, with
+/// [`write_before_modify_before_read`][crate::ordering::write_before_modify_before_read].
+pub struct Modifies<T: 'static>(FlowSet, PhantomData<fn() -> T>);
+
+impl<T: 'static> Default for Modifies<T> {
+    fn default() -> Self {
+        Self(FlowSet::of::<T>(FlowAccess::Modify), PhantomData)
+    }
+}
+
+impl<T> Debug for Modifies<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Modifies").field(&type_name::<T>()).finish()
+    }
+}
+
+impl<T> Hash for Modifies<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<T> Clone for Modifies<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Modifies<T> {}
+
+impl<T> PartialEq for Modifies<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for Modifies<T> {}
+
+impl<T> SystemSet for Modifies<T> {
+    fn dyn_clone(&self) -> Box<dyn SystemSet> {
+        Box::new(*self)
+    }
+
+    fn as_dyn_eq(&self) -> &dyn label::DynEq {
+        &self.0
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<FlowSet>().hash(&mut state);
+        self.0.hash(&mut state);
+    }
+}
+
+/// System set marking all systems that reads value of a target known only at runtime.
+///
+/// Set created for `TypeId::of::<T>()` is the same set as [`Reads<T>`][Reads], and so is the one
+/// created for the [`ComponentId`] of `T` with [`FlowTarget::component`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReadsDyn(FlowSet);
+
+impl ReadsDyn {
+    /// Creates the set for `target`, a [`TypeId`] or a [`FlowTarget`].
+    pub fn new(target: impl Into<FlowTarget>) -> Self {
+        Self(FlowSet::dynamic(FlowAccess::Read, target.into()))
+    }
+}
+
+impl Debug for ReadsDyn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ReadsDyn").field(&self.0.target).finish()
+    }
+}
+
+impl SystemSet for ReadsDyn {
+    fn dyn_clone(&self) -> Box<dyn SystemSet> {
+        Box::new(*self)
+    }
+
+    fn as_dyn_eq(&self) -> &dyn label::DynEq {
+        &self.0
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<FlowSet>().hash(&mut state);
+        self.0.hash(&mut state);
+    }
+}
+
+/// System set for all systems that writes to a target known only at runtime.
+///
+/// Set created for `TypeId::of::<T>()` is the same set as [`Writes<T>`][Writes], and so is the one
+/// created for the [`ComponentId`] of `T` with [`FlowTarget::component`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WritesDyn(FlowSet);
+
+impl WritesDyn {
+    /// Creates the set for `target`, a [`TypeId`] or a [`FlowTarget`].
+    pub fn new(target: impl Into<FlowTarget>) -> Self {
+        Self(FlowSet::dynamic(FlowAccess::Write, target.into()))
+    }
+}
+
+impl Debug for WritesDyn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WritesDyn").field(&self.0.target).finish()
+    }
+}
+
+impl SystemSet for WritesDyn {
+    fn dyn_clone(&self) -> Box<dyn SystemSet> {
+        Box::new(*self)
+    }
+
+    fn as_dyn_eq(&self) -> &dyn label::DynEq {
+        &self.0
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<FlowSet>().hash(&mut state);
+        self.0.hash(&mut state);
+    }
+}
+
+/// System set for all systems that modify a target known only at runtime.
+///
+/// Set created for `TypeId::of::<T>()` is the same set as [`Modifies<T>`][Modifies], and so is
+/// the one created for the [`ComponentId`] of `T` with [`FlowTarget::component`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModifiesDyn(FlowSet);
+
+impl ModifiesDyn {
+    /// Creates the set for `target`, a [`TypeId`] or a [`FlowTarget`].
+    pub fn new(target: impl Into<FlowTarget>) -> Self {
+        Self(FlowSet::dynamic(FlowAccess::Modify, target.into()))
+    }
+}
+
+impl Debug for ModifiesDyn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ModifiesDyn").field(&self.0.target).finish()
+    }
+}
+
+impl SystemSet for ModifiesDyn {
+    fn dyn_clone(&self) -> Box<dyn SystemSet> {
+        Box::new(*self)
+    }
+
+    fn as_dyn_eq(&self) -> &dyn label::DynEq {
+        &self.0
     }
 
     fn dyn_hash(&self, mut state: &mut dyn Hasher) {
-        TypeId::of::<Self>().hash(&mut state);
+        TypeId::of::<FlowSet>().hash(&mut state);
+        self.0.hash(&mut state);
+    }
+}
+
+/// System set for all systems that may read anything, like the ones taking `&World`.
+///
+/// Constraints like [`write_before_read`][crate::ordering::write_before_read] nest it in the
+/// reading set of their type, so its systems are ordered and listed as readers of it.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReadsAll;
+
+/// System set for all systems that may write anything, like exclusive systems taking
+/// `&mut World`.
+///
+/// Constraints like [`write_before_read`][crate::ordering::write_before_read] nest it in the
+/// writing set of their type, so its systems are ordered and listed as writers of it. If you know
+/// what an exclusive system writes to, declare it with
+/// [`.exclusive_flow()`][ExclusiveFlow::exclusive_flow] instead.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WritesAll;
+
+/// System set linking an event type to its [`Events`] resource. [`.in_auto_sets()`] puts systems
+/// taking `EventReader<E>` or `EventWriter<E>` in it, so reports can tell events apart from other
+/// data and access to `Events<E>` is matched with the flow sets of `E`.
+///
+/// Configure it in schedules where the event is only used by systems without inferred auto-sets,
+/// like ones sorted by [`FlowSortingPass`][crate::access::FlowSortingPass]:
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # #[derive(Event)]
+/// # struct Hit;
+/// # let mut app = App::new();
+/// app.configure_sets(Update, (EventFlow::of::<Hit>(), write_before_read::<Hit>()));
+/// ```
+///
+/// [`Events`]: bevy::prelude::Events
+/// [`.in_auto_sets()`]: crate::automagic::InferFlow::in_auto_sets
+#[derive(SystemSet, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventFlow {
+    event: TypeId,
+    resource: TypeId,
+    name: &'static str,
+}
+
+impl EventFlow {
+    /// Creates the set for events of type `E`.
+    #[must_use]
+    pub fn of<E: Event>() -> Self {
+        Self {
+            event: TypeId::of::<E>(),
+            resource: TypeId::of::<Events<E>>(),
+            name: any::type_name::<E>(),
+        }
+    }
+
+    /// Returns the target of flow sets of the event.
+    pub(crate) fn target(self) -> FlowTarget {
+        FlowTarget::Type(self.event)
+    }
+
+    /// Returns the id of the `Events<E>` resource type.
+    pub(crate) fn resource(self) -> TypeId {
+        self.resource
+    }
+}
+
+impl Debug for EventFlow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EventFlow")
+            .field(&ShortName(self.name))
+            .finish()
     }
 }
 
@@ -119,13 +503,57 @@ where
 {
     /// Specifies that system reads from T
     fn reads<T: 'static>(self) -> ScheduleConfigs<S> {
+        note_flow_set(Reads::<T>::default().intern());
         self.in_set(Reads::<T>::default())
     }
 
     /// Specifies that system writes to T
     fn writes<T: 'static>(self) -> ScheduleConfigs<S> {
+        note_flow_set(Writes::<T>::default().intern());
         self.in_set(Writes::<T>::default())
     }
+
+    /// Specifies that system reads T and writes a new value based on it
+    fn modifies<T: 'static>(self) -> ScheduleConfigs<S> {
+        note_flow_set(Modifies::<T>::default().intern());
+        self.in_set(Modifies::<T>::default())
+    }
+
+    /// Specifies that system writes to T, with `priority` deciding its position among writers
+    /// chained by
+    /// [`serialize_writers`][crate::serialize::SerializeWritersAppExt::serialize_writers].
+    /// Writers with lower priority run first.
+    fn writes_with_priority<T: 'static>(self, priority: i32) -> ScheduleConfigs<S> {
+        self.writes::<T>()
+            .in_set(WritePriority::new(TypeId::of::<T>(), priority))
+    }
+
+    /// Specifies that system reads from `target`, a [`TypeId`] or a [`FlowTarget`]
+    fn reads_dyn(self, target: impl Into<FlowTarget>) -> ScheduleConfigs<S> {
+        self.in_set(ReadsDyn::new(target))
+    }
+
+    /// Specifies that system writes to `target`, a [`TypeId`] or a [`FlowTarget`]
+    fn writes_dyn(self, target: impl Into<FlowTarget>) -> ScheduleConfigs<S> {
+        self.in_set(WritesDyn::new(target))
+    }
+
+    /// Specifies that system modifies `target`, a [`TypeId`] or a [`FlowTarget`]
+    fn modifies_dyn(self, target: impl Into<FlowTarget>) -> ScheduleConfigs<S> {
+        self.in_set(ModifiesDyn::new(target))
+    }
+
+    /// Dynamic version of [`writes_with_priority`][Self::writes_with_priority] for `target`,
+    /// a [`TypeId`] or a [`FlowTarget`]
+    fn writes_with_priority_dyn(
+        self,
+        target: impl Into<FlowTarget>,
+        priority: i32,
+    ) -> ScheduleConfigs<S> {
+        let target = target.into();
+        self.in_set(WritesDyn::new(target))
+            .in_set(WritePriority::new(target, priority))
+    }
 }
 
 impl<M, S, I> IntoSystemRW<S, M> for I
@@ -134,3 +562,56 @@ where
     I: IntoScheduleConfigs<S, M>,
 {
 }
+
+/// Tuple of types that a system writes to.
+pub trait FlowTypes {
+    /// Adds the system to [`Writes`] set of each type.
+    fn writes<S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
+    where
+        S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>;
+}
+
+macro_rules! impl_flow_types {
+    ( $($ty: ident),* ) => {
+        impl<$($ty: 'static),*> FlowTypes for ($($ty,)*) {
+            fn writes<S>(sys: ScheduleConfigs<S>) -> ScheduleConfigs<S>
+            where
+                S: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+            {
+                $( let sys = sys.writes::<$ty>(); )*
+                sys
+            }
+        }
+    };
+}
+
+all_tuples!(impl_flow_types, 1, 15, T);
+
+/// Extension trait for exclusive systems allowing to declare what they write to.
+pub trait ExclusiveFlow<M> {
+    /// Specifies that the exclusive system writes to each type of the tuple `F`, so constraints
+    /// like [`write_before_read`][crate::ordering::write_before_read] order it like any other
+    /// writer. The system is not added to [`WritesAll`].
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_sorting::prelude::*;
+    /// # #[derive(Resource)]
+    /// # struct Level;
+    /// # #[derive(Resource)]
+    /// # struct Navmesh;
+    /// # fn rebuild_level(world: &mut World) {}
+    /// # let mut app = App::new();
+    /// app.add_systems(Update, rebuild_level.exclusive_flow::<(Level, Navmesh)>());
+    /// ```
+    fn exclusive_flow<F: FlowTypes>(self) -> ScheduleConfigs<ScheduleSystem>;
+}
+
+impl<T, M, SM> ExclusiveFlow<(M, SM)> for T
+where
+    T: ExclusiveSystemParamFunction<M> + IntoScheduleConfigs<ScheduleSystem, SM>,
+{
+    fn exclusive_flow<F: FlowTypes>(self) -> ScheduleConfigs<ScheduleSystem> {
+        F::writes(self.into_configs())
+    }
+}
@@ -4,17 +4,103 @@ use bevy::{
     ecs::intern::Interned,
     prelude::{IntoScheduleConfigs as _, SystemSet},
 };
-use markers::{Reads, Writes};
+use markers::{
+    FlowTarget, Modifies, ModifiesDyn, Reads, ReadsAll, ReadsDyn, Writes, WritesAll, WritesDyn,
+};
 /// Constraint for `App::configure_systems` specifying that all writes to T must be executed before
-/// the first read
+/// the first read. Systems modifying T count as writers, and so do systems in
+/// [`WritesAll`][markers::WritesAll]. Systems in [`ReadsAll`][markers::ReadsAll] count as readers.
 #[must_use]
 pub fn write_before_read<T: 'static>() -> ScheduleConfigs<Interned<dyn SystemSet>> {
-    Writes::<T>::default().before(Reads::<T>::default())
+    (
+        (Writes::<T>::default(), Modifies::<T>::default()).before(Reads::<T>::default()),
+        barriers(Writes::<T>::default(), Reads::<T>::default()),
+    )
+        .into_configs()
 }
 
 /// Constraint for `App::configure_systems` specifying that all reads from T must be executed
-/// before the first write
+/// before the first write. Systems modifying T count as writers, and so do systems in
+/// [`WritesAll`][markers::WritesAll]. Systems in [`ReadsAll`][markers::ReadsAll] count as readers.
 #[must_use]
 pub fn read_before_write<T: 'static>() -> ScheduleConfigs<Interned<dyn SystemSet>> {
-    Reads::<T>::default().before(Writes::<T>::default())
+    (
+        (Writes::<T>::default(), Modifies::<T>::default()).after(Reads::<T>::default()),
+        barriers(Writes::<T>::default(), Reads::<T>::default()),
+    )
+        .into_configs()
+}
+
+/// Constraint for `App::configure_systems` specifying that systems producing T run first, then
+/// systems modifying it, and then systems reading it. Systems in [`WritesAll`][markers::WritesAll]
+/// count as producers and systems in [`ReadsAll`][markers::ReadsAll] as readers.
+#[must_use]
+pub fn write_before_modify_before_read<T: 'static>() -> ScheduleConfigs<Interned<dyn SystemSet>> {
+    (
+        (
+            Writes::<T>::default(),
+            Modifies::<T>::default(),
+            Reads::<T>::default(),
+        )
+            .chain(),
+        barriers(Writes::<T>::default(), Reads::<T>::default()),
+    )
+        .into_configs()
+}
+
+/// Dynamic version of [`write_before_read`] for `target`, a [`TypeId`][std::any::TypeId] or a
+/// [`FlowTarget`]
+#[must_use]
+pub fn write_before_read_dyn(
+    target: impl Into<FlowTarget>,
+) -> ScheduleConfigs<Interned<dyn SystemSet>> {
+    let target = target.into();
+    (
+        (WritesDyn::new(target), ModifiesDyn::new(target)).before(ReadsDyn::new(target)),
+        barriers(WritesDyn::new(target), ReadsDyn::new(target)),
+    )
+        .into_configs()
+}
+
+/// Dynamic version of [`read_before_write`] for `target`, a [`TypeId`][std::any::TypeId] or a
+/// [`FlowTarget`]
+#[must_use]
+pub fn read_before_write_dyn(
+    target: impl Into<FlowTarget>,
+) -> ScheduleConfigs<Interned<dyn SystemSet>> {
+    let target = target.into();
+    (
+        (WritesDyn::new(target), ModifiesDyn::new(target)).after(ReadsDyn::new(target)),
+        barriers(WritesDyn::new(target), ReadsDyn::new(target)),
+    )
+        .into_configs()
+}
+
+/// Dynamic version of [`write_before_modify_before_read`] for `target`, a
+/// [`TypeId`][std::any::TypeId] or a [`FlowTarget`]
+#[must_use]
+pub fn write_before_modify_before_read_dyn(
+    target: impl Into<FlowTarget>,
+) -> ScheduleConfigs<Interned<dyn SystemSet>> {
+    let target = target.into();
+    (
+        (
+            WritesDyn::new(target),
+            ModifiesDyn::new(target),
+            ReadsDyn::new(target),
+        )
+            .chain(),
+        barriers(WritesDyn::new(target), ReadsDyn::new(target)),
+    )
+        .into_configs()
+}
+
+/// Puts [`WritesAll`] in the writing set and [`ReadsAll`] in the reading set of a target, so
+/// systems that may touch anything are ordered like any other writer or reader of it. Nesting the
+/// sets, instead of ordering them, keeps constraints on different targets independent.
+fn barriers(
+    writes: impl SystemSet,
+    reads: impl SystemSet,
+) -> ScheduleConfigs<Interned<dyn SystemSet>> {
+    (WritesAll.in_set(writes), ReadsAll.in_set(reads)).into_configs()
 }
@@ -0,0 +1,335 @@
+use std::{
+    any::TypeId,
+    collections::{HashMap, HashSet},
+};
+
+use bevy::{
+    ecs::schedule::{
+        graph::DiGraph, InternedScheduleLabel, NodeId, ScheduleBuildError, ScheduleBuildPass,
+        ScheduleGraph, ScheduleLabel,
+    },
+    prelude::{App, Plugin, Resource, World},
+};
+
+use crate::{
+    access::build_only_pass,
+    flow_graph::{event_resources, FlowGraph},
+    markers::{AnyFlowSet, FlowTarget},
+    schedules::add_pass_to_all_schedules,
+};
+
+use bevy_utils_proc_macros::all_tuples;
+
+#[cfg(test)]
+mod tests;
+
+/// Order of the systems writing and reading the same data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum FlowOrder {
+    /// Writers run before readers, like with [`write_before_read`][crate::ordering::write_before_read].
+    #[default]
+    WriteBeforeRead,
+    /// Readers run before writers, like with [`read_before_write`][crate::ordering::read_before_write].
+    ReadBeforeWrite,
+    /// Writers and readers are not ordered.
+    Unordered,
+}
+
+/// Plugin applying a default [`FlowOrder`] to every event read or written by systems with
+/// [`.in_auto_sets()`][crate::automagic::InferFlow::in_auto_sets] in the given schedules, so
+/// there is no need to call [`write_before_read`][crate::ordering::write_before_read] for each of
+/// them.
+///
+/// The order can be overridden for a single type, which also works for types other than events.
+/// Types whose flow sets already have any constraint configured in a schedule are left alone.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # let mut app = App::new();
+/// app.add_plugins(
+///     SortingPlugin::new(Update)
+///         .with_schedule(FixedUpdate)
+///         .with_order::<AppExit>(FlowOrder::ReadBeforeWrite),
+/// );
+/// ```
+pub struct SortingPlugin {
+    schedules: Vec<InternedScheduleLabel>,
+    events: FlowOrder,
+    overrides: HashMap<FlowTarget, FlowOrder>,
+}
+
+#[allow(clippy::needless_pass_by_value)]
+impl SortingPlugin {
+    /// Creates the plugin ordering writers of every event before its readers in `schedule`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedules: vec![schedule.intern()],
+            events: FlowOrder::default(),
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Applies the policy in `schedule` too.
+    #[must_use]
+    pub fn with_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedules.push(schedule.intern());
+        self
+    }
+
+    /// Sets the default order of writers and readers of events.
+    #[must_use]
+    pub fn with_events(mut self, order: FlowOrder) -> Self {
+        self.events = order;
+        self
+    }
+
+    /// Sets the order of writers and readers of `T`, overriding the default.
+    #[must_use]
+    pub fn with_order<T: 'static>(self, order: FlowOrder) -> Self {
+        self.with_order_dyn(TypeId::of::<T>(), order)
+    }
+
+    /// Sets the order of writers and readers of `target`, overriding the default.
+    #[must_use]
+    pub fn with_order_dyn(mut self, target: impl Into<FlowTarget>, order: FlowOrder) -> Self {
+        self.overrides.insert(target.into(), order);
+        self
+    }
+}
+
+impl Plugin for SortingPlugin {
+    fn build(&self, app: &mut App) {
+        let mut policies = app.world_mut().get_resource_or_init::<FlowPolicies>();
+        for &schedule in &self.schedules {
+            let policy = policies.schedules.entry(schedule).or_default();
+            policy.events = self.events;
+            policy.overrides.extend(&self.overrides);
+        }
+        for &label in &self.schedules {
+            app.edit_schedule(label, |schedule| {
+                schedule.add_build_pass(FlowPolicyPass { schedule: label });
+            });
+        }
+    }
+
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
+
+/// Default order of writers and readers of a type, applied by [`FlowPolicyPlugin`] to every
+/// schedule where the type appears in a [`Reads`][crate::markers::Reads] or
+/// [`Writes`][crate::markers::Writes] set.
+///
+/// Implement it with the `#[flow(...)]` attribute, taking `write_before_read`,
+/// `read_before_write` or `unordered`, and register the types with
+/// [`register_flow_policy`][FlowPolicyAppExt::register_flow_policy]:
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// #[derive(Event)]
+/// #[flow(write_before_read)]
+/// struct LevelUpEvent;
+/// # #[derive(Event)]
+/// # #[flow(read_before_write)]
+/// # struct GameOverEvent;
+///
+/// # let mut app = App::new();
+/// app.register_flow_policy::<(LevelUpEvent, GameOverEvent)>();
+/// ```
+pub trait FlowPolicy: 'static {
+    /// Order of the systems writing and reading the type.
+    const ORDER: FlowOrder;
+}
+
+/// Type with a [`FlowPolicy`], or tuple of such types.
+pub trait FlowPolicyTypes {
+    /// Returns ids of the types with their orders.
+    fn orders() -> Vec<(TypeId, FlowOrder)>;
+}
+
+impl<T: FlowPolicy> FlowPolicyTypes for T {
+    fn orders() -> Vec<(TypeId, FlowOrder)> {
+        vec![(TypeId::of::<T>(), T::ORDER)]
+    }
+}
+
+macro_rules! impl_flow_policy_types {
+    ( $($ty: ident),* ) => {
+        impl<$($ty: FlowPolicy),*> FlowPolicyTypes for ($($ty,)*) {
+            fn orders() -> Vec<(TypeId, FlowOrder)> {
+                vec![$( (TypeId::of::<$ty>(), $ty::ORDER) ),*]
+            }
+        }
+    };
+}
+
+all_tuples!(impl_flow_policy_types, 1, 15, T);
+
+/// Extension trait for [`App`] registering types with a [`FlowPolicy`].
+pub trait FlowPolicyAppExt {
+    /// Registers the policy of `T`, or of each type of the tuple `T`, and adds
+    /// [`FlowPolicyPlugin`] if it is missing. A policy takes precedence over the default order for
+    /// events set by [`SortingPlugin`], but not over its per-type orders.
+    fn register_flow_policy<T: FlowPolicyTypes>(&mut self) -> &mut Self;
+}
+
+impl FlowPolicyAppExt for App {
+    fn register_flow_policy<T: FlowPolicyTypes>(&mut self) -> &mut Self {
+        if !self.is_plugin_added::<FlowPolicyPlugin>() {
+            self.add_plugins(FlowPolicyPlugin);
+        }
+        self.world_mut()
+            .get_resource_or_init::<FlowPolicies>()
+            .types
+            .extend(
+                T::orders()
+                    .into_iter()
+                    .map(|(type_id, order)| (type_id.into(), order)),
+            );
+        self
+    }
+}
+
+/// Plugin applying the [`FlowPolicy`] of every registered type to all schedules of the app. It is
+/// added by [`register_flow_policy`][FlowPolicyAppExt::register_flow_policy].
+///
+/// Schedules created after the app is finished get the policy only when added with
+/// [`add_flow_schedule`][crate::schedules::FlowScheduleExt::add_flow_schedule].
+#[derive(Debug, Default)]
+pub struct FlowPolicyPlugin;
+
+impl Plugin for FlowPolicyPlugin {
+    fn build(&self, app: &mut App) {
+        app.world_mut().get_resource_or_init::<FlowPolicies>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        add_pass_to_all_schedules(app, |schedule| FlowPolicyPass { schedule });
+    }
+}
+
+/// Orders configured by [`SortingPlugin`] and [`FlowPolicy`] registrations.
+#[derive(Resource, Debug, Default)]
+struct FlowPolicies {
+    schedules: HashMap<InternedScheduleLabel, SchedulePolicy>,
+    types: HashMap<FlowTarget, FlowOrder>,
+}
+
+/// Orders configured by [`SortingPlugin`] for a single schedule.
+#[derive(Debug, Default)]
+struct SchedulePolicy {
+    events: FlowOrder,
+    overrides: HashMap<FlowTarget, FlowOrder>,
+}
+
+impl FlowPolicies {
+    /// Returns the order of writers and readers of `target` in `schedule`. `is_event` tells if the
+    /// target is an event.
+    fn order(
+        &self,
+        schedule: InternedScheduleLabel,
+        target: FlowTarget,
+        is_event: bool,
+    ) -> FlowOrder {
+        let policy = self.schedules.get(&schedule);
+        if let Some(&order) = policy.and_then(|policy| policy.overrides.get(&target)) {
+            return order;
+        }
+        if let Some(&order) = self.types.get(&target) {
+            return order;
+        }
+        match policy {
+            Some(policy) if is_event => policy.events,
+            _ => FlowOrder::Unordered,
+        }
+    }
+}
+
+/// Schedule build pass ordering writers and readers of each type according to [`FlowPolicies`].
+#[derive(Debug)]
+struct FlowPolicyPass {
+    schedule: InternedScheduleLabel,
+}
+
+impl ScheduleBuildPass for FlowPolicyPass {
+    build_only_pass!();
+
+    fn build(
+        &mut self,
+        world: &mut World,
+        graph: &mut ScheduleGraph,
+        dependency_flattened: &mut DiGraph,
+    ) -> Result<(), ScheduleBuildError> {
+        let Some(policies) = world.get_resource::<FlowPolicies>() else {
+            return Ok(());
+        };
+        let flow = FlowGraph::extract_in(world, graph);
+        let events = event_resources(graph, world.components());
+        let constrained = flow
+            .constraints
+            .iter()
+            .flat_map(|&(before, after)| [before, after])
+            .collect::<HashSet<_>>();
+
+        let mut targets = HashMap::<FlowTarget, (Vec<NodeId>, Vec<NodeId>)>::new();
+        for &(id, kind, _) in &flow.sets {
+            let AnyFlowSet::Flow(set) = kind else {
+                continue;
+            };
+            let (readers, writers) = targets.entry(set.target).or_default();
+            if set.access.writes() {
+                writers.push(id);
+            } else {
+                readers.push(id);
+            }
+        }
+
+        for (target, (readers, writers)) in targets {
+            if readers.is_empty() || writers.is_empty() {
+                continue;
+            }
+            if readers
+                .iter()
+                .chain(&writers)
+                .any(|set| constrained.contains(set))
+            {
+                continue;
+            }
+            let is_event = events.values().any(|&event| event == target);
+            let (first, second) = match policies.order(self.schedule, target, is_event) {
+                FlowOrder::WriteBeforeRead => (writers, readers),
+                FlowOrder::ReadBeforeWrite => (readers, writers),
+                FlowOrder::Unordered => continue,
+            };
+            order_members(&flow, dependency_flattened, &first, &second);
+        }
+        Ok(())
+    }
+}
+
+/// Adds edges to `dependency_flattened`, so members of `first` sets run before members of `second`
+/// sets. Systems belonging to both sides are not ordered among themselves.
+fn order_members(
+    flow: &FlowGraph,
+    dependency_flattened: &mut DiGraph,
+    first: &[NodeId],
+    second: &[NodeId],
+) {
+    let members = |sets: &[NodeId]| {
+        sets.iter()
+            .flat_map(|&set| flow.members(set))
+            .collect::<Vec<_>>()
+    };
+    let first = members(first);
+    let second = members(second);
+    for &before in &first {
+        for &after in &second {
+            if before != after && !(first.contains(&after) && second.contains(&before)) {
+                dependency_flattened.add_edge(before, after);
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    ecs::schedule::{LogLevel, ScheduleBuildError, ScheduleBuildSettings, ScheduleLabel},
+    prelude::*,
+};
+
+use super::{FlowOrder, FlowPolicyAppExt, FlowPolicyPlugin, SortingPlugin};
+use crate::prelude::{flow, read_before_write, FlowScheduleExt, InferFlow};
+
+#[test]
+fn events_are_written_before_read() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        app.add_plugins(SortingPlugin::new(Update));
+        deny_ambiguities(&mut app);
+        app.add_event::<Hit>();
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Update,
+            (move |_reader: EventReader<Hit>| trace_handle.lock().unwrap().push("read"))
+                .in_auto_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Update,
+            (move |_writer: EventWriter<Hit>| trace_handle.lock().unwrap().push("write"))
+                .in_auto_sets(),
+        );
+
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["write", "read"]);
+    }
+}
+
+#[test]
+fn override_reverses_order() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        app.add_plugins(SortingPlugin::new(Update).with_order::<Hit>(FlowOrder::ReadBeforeWrite));
+        deny_ambiguities(&mut app);
+        app.add_event::<Hit>();
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Update,
+            (move |_writer: EventWriter<Hit>| trace_handle.lock().unwrap().push("write"))
+                .in_auto_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Update,
+            (move |_reader: EventReader<Hit>| trace_handle.lock().unwrap().push("read"))
+                .in_auto_sets(),
+        );
+
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["read", "write"]);
+    }
+}
+
+#[test]
+fn explicit_constraint_wins() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        app.add_plugins(SortingPlugin::new(Update));
+        deny_ambiguities(&mut app);
+        app.add_event::<Hit>();
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Update,
+            (move |_writer: EventWriter<Hit>| trace_handle.lock().unwrap().push("write"))
+                .in_auto_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Update,
+            (move |_reader: EventReader<Hit>| trace_handle.lock().unwrap().push("read"))
+                .in_auto_sets(),
+        );
+
+        app.configure_sets(Update, read_before_write::<Hit>());
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["read", "write"]);
+    }
+}
+
+#[test]
+fn other_schedules_are_not_affected() {
+    let mut app = App::new();
+    app.add_plugins(SortingPlugin::new(Update));
+    deny_ambiguities(&mut app);
+    app.add_event::<Hit>();
+
+    app.add_systems(PostUpdate, (|_reader: EventReader<Hit>| {}).in_auto_sets());
+    app.add_systems(PostUpdate, (|_writer: EventWriter<Hit>| {}).in_auto_sets());
+
+    let result = app
+        .world_mut()
+        .try_schedule_scope(PostUpdate, |world, schedule| schedule.initialize(world))
+        .unwrap();
+    assert!(matches!(result, Err(ScheduleBuildError::Ambiguity(_))));
+}
+
+#[test]
+fn type_policy_applies_to_all_schedules() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        app.register_flow_policy::<Score>();
+        deny_ambiguities(&mut app);
+        app.init_resource::<Score>();
+
+        for (schedule, read, write) in [
+            (Update.intern(), "update read", "update write"),
+            (PostUpdate.intern(), "post update read", "post update write"),
+        ] {
+            let trace_handle = Arc::clone(&trace);
+            app.add_systems(
+                schedule,
+                (move |_score: ResMut<Score>| trace_handle.lock().unwrap().push(write))
+                    .in_auto_sets(),
+            );
+
+            let trace_handle = Arc::clone(&trace);
+            app.add_systems(
+                schedule,
+                (move |_score: Res<Score>| trace_handle.lock().unwrap().push(read)).in_auto_sets(),
+            );
+        }
+
+        app.run();
+
+        assert_eq!(
+            *trace.lock().unwrap(),
+            vec![
+                "update read",
+                "update write",
+                "post update read",
+                "post update write"
+            ]
+        );
+    }
+}
+
+#[test]
+fn type_policy_takes_precedence_over_events_default() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        app.add_plugins((SortingPlugin::new(Update), FlowPolicyPlugin))
+            .register_flow_policy::<Exit>();
+        deny_ambiguities(&mut app);
+        app.add_event::<Exit>();
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Update,
+            (move |_writer: EventWriter<Exit>| trace_handle.lock().unwrap().push("write"))
+                .in_auto_sets(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Update,
+            (move |_reader: EventReader<Exit>| trace_handle.lock().unwrap().push("read"))
+                .in_auto_sets(),
+        );
+
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["read", "write"]);
+    }
+}
+
+#[test]
+fn type_policy_applies_to_schedules_created_later() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+        app.register_flow_policy::<(Score, Exit)>()
+            .init_resource::<Score>();
+        app.finish();
+        app.cleanup();
+        app.update();
+
+        let mut schedule = Schedule::new(Late);
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: LogLevel::Error,
+            ..default()
+        });
+        let trace_handle = Arc::clone(&trace);
+        schedule.add_systems(
+            (move |_score: ResMut<Score>| trace_handle.lock().unwrap().push("write"))
+                .in_auto_sets(),
+        );
+        let trace_handle = Arc::clone(&trace);
+        schedule.add_systems(
+            (move |_score: Res<Score>| trace_handle.lock().unwrap().push("read")).in_auto_sets(),
+        );
+        app.add_flow_schedule(schedule);
+        app.world_mut().run_schedule(Late);
+
+        assert_eq!(*trace.lock().unwrap(), vec!["read", "write"]);
+    }
+}
+
+fn deny_ambiguities(app: &mut App) {
+    for schedule in [Update.intern(), PostUpdate.intern()] {
+        app.edit_schedule(schedule, |schedule| {
+            schedule.set_build_settings(ScheduleBuildSettings {
+                ambiguity_detection: LogLevel::Error,
+                ..default()
+            });
+        });
+    }
+}
+
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct Late;
+
+#[derive(Event)]
+struct Hit;
+
+#[derive(Resource, Default)]
+#[flow(read_before_write)]
+struct Score;
+
+#[derive(Event)]
+#[flow(read_before_write)]
+struct Exit;
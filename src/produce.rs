@@ -0,0 +1,100 @@
+use std::{any::TypeId, marker::PhantomData};
+
+use bevy::{
+    ecs::{
+        schedule::{graph::GraphInfo, Chain, Schedulable, ScheduleConfigs},
+        system::SystemInput,
+    },
+    prelude::IntoSystem,
+};
+
+use crate::{automagic::InferableSystem, flow_graph::record_produced};
+
+/// Extension trait for systems that overwrite data without reading it first.
+pub trait IntoSystemProducing<I, O, M>: IntoSystem<I, O, M>
+where
+    I: SystemInput,
+{
+    /// Declares that the system only produces new values of `T`, so
+    /// [`.in_auto_sets()`][crate::automagic::InferFlow::in_auto_sets] puts it in
+    /// [`Writes<T>`][crate::markers::Writes] instead of [`Modifies<T>`][crate::markers::Modifies]
+    /// for `ResMut<T>`, `NonSendMut<T>` and `&mut T` in queries. Call it before `.in_auto_sets()`:
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_sorting::prelude::*;
+    /// # #[derive(Resource)]
+    /// # struct Score;
+    /// # fn reset_score(score: ResMut<Score>) {}
+    /// # let mut app = App::new();
+    /// app.add_systems(Update, reset_score.produces::<Score>().in_auto_sets());
+    /// ```
+    ///
+    /// The order is enforced by the types, since `.in_auto_sets()` returns schedule configs rather
+    /// than a system:
+    ///
+    /// ```rust,compile_fail
+    /// # use bevy::prelude::*;
+    /// # use bevy_sorting::prelude::*;
+    /// # #[derive(Resource)]
+    /// # struct Score;
+    /// # fn reset_score(score: ResMut<Score>) {}
+    /// # let mut app = App::new();
+    /// app.add_systems(Update, reset_score.in_auto_sets().produces::<Score>());
+    /// ```
+    ///
+    /// For systems with manually specified flow, use [`.writes::<T>()`][crate::markers::IntoSystemRW::writes]
+    /// instead.
+    fn produces<T: 'static>(self) -> IntoProducing<Self, T>
+    where
+        Self: Sized,
+    {
+        IntoProducing {
+            system: self,
+            produced: PhantomData,
+        }
+    }
+}
+
+impl<S, I, O, M> IntoSystemProducing<I, O, M> for S
+where
+    S: IntoSystem<I, O, M>,
+    I: SystemInput,
+{
+}
+
+/// An [`IntoSystem`] whose mutable access to `T` is inferred as [`Writes<T>`][crate::markers::Writes],
+/// returned by [`.produces()`][IntoSystemProducing::produces].
+pub struct IntoProducing<S, T> {
+    system: S,
+    produced: PhantomData<fn() -> T>,
+}
+
+#[doc(hidden)]
+pub struct IsProducingSystem;
+
+impl<S, T, I, O, M> IntoSystem<I, O, (IsProducingSystem, M)> for IntoProducing<S, T>
+where
+    S: IntoSystem<I, O, M>,
+    I: SystemInput,
+    T: 'static,
+{
+    type System = S::System;
+
+    fn into_system(this: Self) -> Self::System {
+        IntoSystem::into_system(this.system)
+    }
+}
+
+impl<S, T, M> InferableSystem<(IsProducingSystem, M)> for IntoProducing<S, T>
+where
+    S: InferableSystem<M>,
+    T: 'static,
+{
+    fn apply<Sch>(sys: ScheduleConfigs<Sch>) -> ScheduleConfigs<Sch>
+    where
+        Sch: Schedulable<Metadata = GraphInfo, GroupMetadata = Chain>,
+    {
+        record_produced(TypeId::of::<T>(), || S::apply(sys))
+    }
+}
@@ -0,0 +1,217 @@
+use std::any::TypeId;
+
+use bevy::{
+    ecs::{
+        component::Components,
+        schedule::{
+            graph::DiGraph, InternedScheduleLabel, ScheduleBuildError, ScheduleBuildPass,
+            ScheduleGraph, Schedules,
+        },
+    },
+    prelude::{App, Mut, Plugin, Resource, World},
+};
+
+use crate::{
+    access::build_only_pass,
+    flow_graph::{event_resources, target_name, FlowGraph},
+    markers::{AnyFlowSet, FlowTarget},
+    schedules::add_pass_to_all_schedules,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Resource listing every type that takes part in the data flow of the app, that is appears in a
+/// [`Reads`][crate::markers::Reads] or [`Writes`][crate::markers::Writes] set, with the schedules
+/// it appears in.
+///
+/// [`FlowRegistryPlugin`] fills it from all schedules when the app is finished, and keeps it up to
+/// date whenever a schedule is built, which happens on its first run and again after systems are
+/// added to it. Components are listed as [`FlowKind::Marker`] until a schedule using them is built,
+/// since they are only registered in the world then. Sets of components without a rust type
+/// are not listed. Types that only systems sorted by their access
+/// use, without any flow set in the schedule, are not listed.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # #[derive(Resource)]
+/// # struct Score;
+/// # fn update_score(score: ResMut<Score>) {}
+/// # let mut app = App::new();
+/// app.add_plugins(FlowRegistryPlugin)
+///     .add_systems(Update, update_score.in_auto_sets());
+/// app.finish();
+///
+/// assert!(app.world().resource::<FlowRegistry>().get::<Score>().is_some());
+/// for flow_type in app.world().resource::<FlowRegistry>().iter() {
+///     info!("{} ({:?}) in {:?}", flow_type.name, flow_type.kind, flow_type.schedules);
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct FlowRegistry {
+    types: Vec<FlowType>,
+}
+
+/// Type registered in [`FlowRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowType {
+    /// Id of the type.
+    pub type_id: TypeId,
+    /// Full name of the type.
+    pub name: String,
+    /// What the type is used as.
+    pub kind: FlowKind,
+    /// Schedules with a flow set of the type, in the order they were registered in.
+    pub schedules: Vec<InternedScheduleLabel>,
+}
+
+/// What a type in [`FlowRegistry`] is used as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FlowKind {
+    /// Event read or written with `EventReader` or `EventWriter`.
+    Event,
+    /// Resource.
+    Resource,
+    /// Component.
+    Component,
+    /// Non-send resource.
+    NonSend,
+    /// Any other type, used only in manual markers.
+    Marker,
+}
+
+impl FlowRegistry {
+    /// Returns all registered types, in the order they were first seen.
+    pub fn iter(&self) -> impl Iterator<Item = &FlowType> {
+        self.types.iter()
+    }
+
+    /// Returns the entry of `T`, if it takes part in the data flow.
+    #[must_use]
+    pub fn get<T: 'static>(&self) -> Option<&FlowType> {
+        self.get_dyn(TypeId::of::<T>())
+    }
+
+    /// Returns the entry of the type with id `type_id`, if it takes part in the data flow.
+    #[must_use]
+    pub fn get_dyn(&self, type_id: TypeId) -> Option<&FlowType> {
+        self.types
+            .iter()
+            .find(|flow_type| flow_type.type_id == type_id)
+    }
+
+    /// Adds types with flow sets in `graph` of schedule `label`.
+    fn register(
+        &mut self,
+        components: &Components,
+        label: InternedScheduleLabel,
+        graph: &ScheduleGraph,
+    ) {
+        let flow = FlowGraph::extract(graph);
+        let events = event_resources(graph, components);
+        let is_event = |type_id| {
+            events
+                .values()
+                .any(|&event| event == FlowTarget::Type(type_id))
+        };
+        for (_, kind, _) in &flow.sets {
+            let AnyFlowSet::Flow(set) = kind else {
+                continue;
+            };
+            // Components without a rust type have no `TypeId` to be registered under.
+            let FlowTarget::Type(type_id) = set.target else {
+                continue;
+            };
+            let name = target_name(kind);
+
+            let position = self.types.iter().position(|known| known.type_id == type_id);
+            let index = position.unwrap_or_else(|| {
+                self.types.push(FlowType {
+                    type_id,
+                    name: name.clone(),
+                    kind: flow_kind(components, type_id, is_event(type_id)),
+                    schedules: Vec::new(),
+                });
+                self.types.len() - 1
+            });
+            let flow_type = &mut self.types[index];
+            if set.name.is_some() {
+                flow_type.name = name;
+            }
+            if flow_type.kind == FlowKind::Marker {
+                flow_type.kind = flow_kind(components, type_id, is_event(type_id));
+            }
+            if !flow_type.schedules.contains(&label) {
+                flow_type.schedules.push(label);
+            }
+        }
+    }
+}
+
+fn flow_kind(components: &Components, type_id: TypeId, is_event: bool) -> FlowKind {
+    if is_event {
+        return FlowKind::Event;
+    }
+    if let Some(info) = components
+        .get_resource_id(type_id)
+        .and_then(|id| components.get_info(id))
+    {
+        return if info.is_send_and_sync() {
+            FlowKind::Resource
+        } else {
+            FlowKind::NonSend
+        };
+    }
+    if components.get_id(type_id).is_some() {
+        return FlowKind::Component;
+    }
+    FlowKind::Marker
+}
+
+/// Plugin keeping [`FlowRegistry`] up to date for all schedules of the app.
+///
+/// Schedules created after the app is finished are covered only when added with
+/// [`add_flow_schedule`][crate::schedules::FlowScheduleExt::add_flow_schedule].
+#[derive(Debug, Default)]
+pub struct FlowRegistryPlugin;
+
+impl Plugin for FlowRegistryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FlowRegistry>();
+    }
+
+    fn finish(&self, app: &mut App) {
+        add_pass_to_all_schedules(app, |schedule| FlowRegistryPass { schedule });
+        // Schedules are only built on their first run, so the registry is filled from the
+        // configured schedules too, to be usable as soon as the app is finished.
+        app.world_mut()
+            .resource_scope(|world, mut registry: Mut<FlowRegistry>| {
+                for (_, schedule) in world.resource::<Schedules>().iter() {
+                    registry.register(world.components(), schedule.label(), schedule.graph());
+                }
+            });
+    }
+}
+
+/// Schedule build pass adding types with flow sets in the schedule to [`FlowRegistry`].
+#[derive(Debug)]
+struct FlowRegistryPass {
+    schedule: InternedScheduleLabel,
+}
+
+impl ScheduleBuildPass for FlowRegistryPass {
+    build_only_pass!();
+
+    fn build(
+        &mut self,
+        world: &mut World,
+        graph: &mut ScheduleGraph,
+        _dependency_flattened: &mut DiGraph,
+    ) -> Result<(), ScheduleBuildError> {
+        world.try_resource_scope(|world, mut registry: Mut<FlowRegistry>| {
+            registry.register(world.components(), self.schedule, graph);
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use assert_unordered::assert_eq_unordered;
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+
+use super::{FlowKind, FlowRegistry, FlowRegistryPlugin};
+use crate::prelude::{FlowScheduleExt, InferFlow, IntoSystemRW};
+
+#[test]
+fn types_are_registered_with_kinds_and_schedules() {
+    let mut app = App::new();
+    app.add_plugins(FlowRegistryPlugin)
+        .add_event::<Hit>()
+        .init_resource::<Score>();
+
+    app.add_systems(
+        Update,
+        (
+            (|_reader: EventReader<Hit>, _score: ResMut<Score>| {}).in_auto_sets(),
+            (|_query: Query<&Health>, _window: NonSend<Window>| {}).in_auto_sets(),
+        ),
+    );
+    app.add_systems(
+        PostUpdate,
+        (|_score: Res<Score>| {})
+            .in_auto_sets()
+            .reads::<PlayerStats>(),
+    );
+    app.world_mut().insert_non_send_resource(Window);
+    app.finish();
+    app.cleanup();
+    app.update();
+
+    let registry = app.world().resource::<FlowRegistry>();
+    let kind = |entry: Option<&super::FlowType>| entry.map(|entry| entry.kind);
+    assert_eq!(kind(registry.get::<Hit>()), Some(FlowKind::Event));
+    assert_eq!(kind(registry.get::<Score>()), Some(FlowKind::Resource));
+    assert_eq!(kind(registry.get::<Health>()), Some(FlowKind::Component));
+    assert_eq!(kind(registry.get::<Window>()), Some(FlowKind::NonSend));
+    assert_eq!(kind(registry.get::<PlayerStats>()), Some(FlowKind::Marker));
+    assert_eq!(registry.get::<Unused>(), None);
+
+    let score = registry.get::<Score>().unwrap();
+    assert!(score.name.ends_with("Score"));
+    assert_eq_unordered!(
+        &score.schedules,
+        &vec![Update.intern(), PostUpdate.intern()]
+    );
+    assert_eq!(
+        registry.get::<PlayerStats>().unwrap().schedules,
+        vec![PostUpdate.intern()]
+    );
+}
+
+#[test]
+fn registry_is_filled_when_the_app_is_finished() {
+    let mut app = App::new();
+    app.add_plugins(FlowRegistryPlugin)
+        .init_resource::<Score>()
+        .add_systems(Update, (|_score: Res<Score>| {}).in_auto_sets());
+    app.finish();
+
+    let registry = app.world().resource::<FlowRegistry>();
+    let score = registry.get::<Score>().unwrap();
+    assert_eq!(score.kind, FlowKind::Resource);
+    assert_eq!(score.schedules, vec![Update.intern()]);
+}
+
+#[test]
+fn schedules_created_later_are_registered() {
+    let mut app = App::new();
+    app.add_plugins(FlowRegistryPlugin);
+    app.finish();
+    app.cleanup();
+    app.update();
+
+    let mut schedule = Schedule::new(Late);
+    schedule.add_systems((|_score: Res<Score>| {}).in_auto_sets());
+    app.add_flow_schedule(schedule).init_resource::<Score>();
+    app.world_mut().run_schedule(Late);
+
+    let registry = app.world().resource::<FlowRegistry>();
+    assert_eq!(
+        registry.get::<Score>().unwrap().schedules,
+        vec![Late.intern()]
+    );
+}
+
+#[test]
+fn schedules_created_by_systems_are_registered() {
+    let mut app = App::new();
+    app.add_plugins(FlowRegistryPlugin)
+        .init_resource::<Score>()
+        .add_systems(Update, |world: &mut World| {
+            let mut schedule = Schedule::new(Late);
+            schedule.add_systems((|_score: Res<Score>| {}).in_auto_sets());
+            world.add_flow_schedule(schedule);
+            world.run_schedule(Late);
+        });
+    app.finish();
+    app.cleanup();
+    app.update();
+
+    let registry = app.world().resource::<FlowRegistry>();
+    assert_eq!(
+        registry.get::<Score>().unwrap().schedules,
+        vec![Late.intern()]
+    );
+}
+
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct Late;
+
+#[derive(Event)]
+struct Hit;
+
+#[derive(Resource, Default)]
+struct Score;
+
+#[derive(Component)]
+struct Health;
+
+struct Window;
+
+struct PlayerStats;
+
+#[derive(Resource)]
+struct Unused;
@@ -0,0 +1,74 @@
+use bevy::{
+    ecs::schedule::{InternedScheduleLabel, Schedule, ScheduleBuildPass, Schedules},
+    prelude::{App, Resource, World},
+};
+
+/// Build passes that plugins of this crate add to every schedule of the app, kept so they can be
+/// added to schedules created after the app is finished.
+#[derive(Resource, Default)]
+struct FlowSchedulePasses(Vec<AddPass>);
+
+type AddPass = Box<dyn Fn(&mut Schedule) + Send + Sync>;
+
+/// Adds the pass created by `pass` to every schedule of the app, and to schedules added later with
+/// [`add_flow_schedule`][FlowScheduleExt::add_flow_schedule].
+pub(crate) fn add_pass_to_all_schedules<P: ScheduleBuildPass>(
+    app: &mut App,
+    pass: fn(InternedScheduleLabel) -> P,
+) {
+    let add_pass = move |schedule: &mut Schedule| {
+        schedule.add_build_pass(pass(schedule.label()));
+    };
+    for (_, schedule) in app.world_mut().resource_mut::<Schedules>().iter_mut() {
+        add_pass(schedule);
+    }
+    app.world_mut()
+        .get_resource_or_init::<FlowSchedulePasses>()
+        .0
+        .push(Box::new(add_pass));
+}
+
+/// Extension trait for [`App`] and [`World`] adding schedules created after the app is finished.
+///
+/// Plugins like [`FlowPolicyPlugin`][crate::policy::FlowPolicyPlugin] and
+/// [`FlowRegistryPlugin`][crate::registry::FlowRegistryPlugin] add their build passes to the
+/// schedules that exist when the app is finished. Schedules created later, for example by a
+/// system, only get them when added with this trait instead of `add_schedule`:
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy::ecs::schedule::ScheduleLabel;
+/// # use bevy_sorting::prelude::*;
+/// # #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// # struct Level;
+/// fn load_level(world: &mut World) {
+///     let mut schedule = Schedule::new(Level);
+///     // ...
+///     world.add_flow_schedule(schedule);
+///     world.run_schedule(Level);
+/// }
+/// ```
+pub trait FlowScheduleExt {
+    /// Adds `schedule` like `add_schedule`, together with the build passes that plugins of this
+    /// crate add to every schedule.
+    fn add_flow_schedule(&mut self, schedule: Schedule) -> &mut Self;
+}
+
+impl FlowScheduleExt for World {
+    fn add_flow_schedule(&mut self, mut schedule: Schedule) -> &mut Self {
+        if let Some(passes) = self.get_resource::<FlowSchedulePasses>() {
+            for add_pass in &passes.0 {
+                add_pass(&mut schedule);
+            }
+        }
+        self.add_schedule(schedule);
+        self
+    }
+}
+
+impl FlowScheduleExt for App {
+    fn add_flow_schedule(&mut self, schedule: Schedule) -> &mut Self {
+        self.world_mut().add_flow_schedule(schedule);
+        self
+    }
+}
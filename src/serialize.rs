@@ -0,0 +1,171 @@
+use std::{any::TypeId, collections::HashMap};
+
+use bevy::{
+    ecs::schedule::{
+        graph::DiGraph, NodeId, ScheduleBuildError, ScheduleBuildPass, ScheduleGraph, ScheduleLabel,
+    },
+    prelude::{App, SystemSet, World},
+};
+
+use crate::{
+    access::{build_only_pass, systems_in_set},
+    flow_graph::FlowGraph,
+    markers::{AnyFlowSet, FlowTarget},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// System set marking a target whose writers are chained in a stable order by
+/// [`SerializeWritersPass`]. Configure it with
+/// [`serialize_writers`][SerializeWritersAppExt::serialize_writers], which adds the pass too.
+/// Configuring the set directly on a [`Schedule`][bevy::prelude::Schedule] has no effect unless the
+/// pass is added to it.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SerializedWriters(FlowTarget);
+
+impl SerializedWriters {
+    /// Creates the set for `target`, a [`TypeId`][std::any::TypeId] or a [`FlowTarget`].
+    pub fn new(target: impl Into<FlowTarget>) -> Self {
+        Self(target.into())
+    }
+
+    pub(crate) fn target(&self) -> FlowTarget {
+        self.0
+    }
+}
+
+/// System set giving its members a position among serialized writers of a target. Systems are
+/// added to it with [`.writes_with_priority()`][crate::markers::IntoSystemRW::writes_with_priority].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WritePriority {
+    target: FlowTarget,
+    priority: i32,
+}
+
+impl WritePriority {
+    /// Creates the set for writers of `target` with `priority`.
+    pub fn new(target: impl Into<FlowTarget>, priority: i32) -> Self {
+        Self {
+            target: target.into(),
+            priority,
+        }
+    }
+}
+
+/// Schedule build pass chaining all writers of each target in a [`SerializedWriters`] set, so they
+/// run in the same order on
+/// every machine. Members of both [`Writes`][crate::markers::Writes] and
+/// [`Modifies`][crate::markers::Modifies] sets are chained, ordered by their priority, then by
+/// system name, and then by the order they were added in. Systems without a priority have
+/// priority `0`. Other systems are not affected.
+///
+/// The order has to agree with other constraints of the schedule, otherwise building it fails with
+/// a dependency cycle.
+///
+/// It is added by [`serialize_writers`][SerializeWritersAppExt::serialize_writers]. Schedules used
+/// without an [`App`] need it added manually:
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # use std::any::TypeId;
+/// # #[derive(Component)]
+/// # struct Health;
+/// # let mut schedule = Schedule::default();
+/// schedule
+///     .configure_sets(SerializedWriters::new(TypeId::of::<Health>()))
+///     .add_build_pass(SerializeWritersPass);
+/// ```
+#[derive(Debug, Default)]
+pub struct SerializeWritersPass;
+
+/// Extension trait for [`App`] serializing writers of a type.
+pub trait SerializeWritersAppExt {
+    /// Makes all systems writing or modifying `T` in `schedule` run one after another, in the same
+    /// order on every run, and adds [`SerializeWritersPass`] to the schedule. The systems are
+    /// ordered by their priority set with
+    /// [`.writes_with_priority()`][crate::markers::IntoSystemRW::writes_with_priority], then by
+    /// name.
+    fn serialize_writers<T: 'static>(&mut self, schedule: impl ScheduleLabel) -> &mut Self;
+
+    /// Dynamic version of [`serialize_writers`][Self::serialize_writers] for `target`, a
+    /// [`TypeId`] or a [`FlowTarget`].
+    fn serialize_writers_dyn(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        target: impl Into<FlowTarget>,
+    ) -> &mut Self;
+}
+
+impl SerializeWritersAppExt for App {
+    fn serialize_writers<T: 'static>(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        self.serialize_writers_dyn(schedule, TypeId::of::<T>())
+    }
+
+    fn serialize_writers_dyn(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        target: impl Into<FlowTarget>,
+    ) -> &mut Self {
+        let label = schedule.intern();
+        self.configure_sets(label, SerializedWriters::new(target))
+            .edit_schedule(label, |schedule| {
+                schedule.add_build_pass(SerializeWritersPass);
+            })
+    }
+}
+
+impl ScheduleBuildPass for SerializeWritersPass {
+    build_only_pass!();
+
+    fn build(
+        &mut self,
+        world: &mut World,
+        graph: &mut ScheduleGraph,
+        dependency_flattened: &mut DiGraph,
+    ) -> Result<(), ScheduleBuildError> {
+        let flow_graph = FlowGraph::extract_in(world, graph);
+        let mut serialized = Vec::new();
+        let mut writers = HashMap::<FlowTarget, Vec<NodeId>>::new();
+        let mut priorities = HashMap::<(FlowTarget, NodeId), i32>::new();
+        for (id, set, _) in graph.system_sets() {
+            let any = set.as_dyn_eq().as_any();
+            if let Some(&SerializedWriters(target)) = any.downcast_ref() {
+                serialized.push(target);
+            } else if let Some(&WritePriority { target, priority }) = any.downcast_ref() {
+                for system in systems_in_set(graph, id) {
+                    let entry = priorities.entry((target, system)).or_insert(priority);
+                    *entry = (*entry).min(priority);
+                }
+            } else if let Some(AnyFlowSet::Flow(flow)) = AnyFlowSet::from_set(set) {
+                if flow.access.writes() {
+                    let members = writers.entry(flow.target).or_default();
+                    for system in flow_graph.members(id) {
+                        if !members.contains(&system) {
+                            members.push(system);
+                        }
+                    }
+                }
+            }
+        }
+
+        for target in serialized {
+            let Some(mut members) = writers.remove(&target) else {
+                continue;
+            };
+            members.sort_by_cached_key(|&system| {
+                let priority = priorities.get(&(target, system)).copied().unwrap_or(0);
+                let name = graph
+                    .get_system_at(system)
+                    .map(|system| system.name())
+                    .unwrap_or_default();
+                (priority, name, system.index())
+            });
+            for pair in members.windows(2) {
+                dependency_flattened.add_edge(pair[0], pair[1]);
+            }
+        }
+        Ok(())
+    }
+}
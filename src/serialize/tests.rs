@@ -0,0 +1,118 @@
+use std::any::TypeId;
+
+use bevy::prelude::*;
+
+use super::{SerializeWritersAppExt, SerializeWritersPass, SerializedWriters};
+use crate::prelude::{write_before_read, InferFlow, IntoSystemRW};
+
+#[test]
+fn writers_are_chained_by_name() {
+    for _ in 0..8 {
+        let mut app = app();
+        app.add_systems(
+            Update,
+            (
+                c_writer.writes::<Marker>(),
+                a_writer.writes::<Marker>(),
+                b_modifier.in_auto_sets(),
+            ),
+        )
+        .serialize_writers::<Marker>(Update);
+        app.update();
+
+        assert_eq!(trace(&app), vec!["a", "b", "c"]);
+    }
+}
+
+#[test]
+fn priority_goes_before_name() {
+    for _ in 0..8 {
+        let mut app = app();
+        app.add_systems(
+            Update,
+            (
+                a_writer.writes_with_priority::<Marker>(2),
+                b_modifier.in_auto_sets(),
+                c_writer.writes_with_priority_dyn(TypeId::of::<Marker>(), -1),
+            ),
+        )
+        .serialize_writers_dyn(Update, TypeId::of::<Marker>());
+        app.update();
+
+        assert_eq!(trace(&app), vec!["c", "b", "a"]);
+    }
+}
+
+#[test]
+fn other_constraints_are_kept() {
+    let mut app = app();
+    app.add_systems(
+        Update,
+        (
+            a_writer.writes::<Marker>(),
+            c_writer.writes::<Marker>(),
+            reader.reads::<Marker>(),
+        ),
+    )
+    .configure_sets(Update, write_before_read::<Marker>())
+    .serialize_writers::<Marker>(Update);
+    app.update();
+
+    assert_eq!(trace(&app), vec!["a", "c", "reader"]);
+}
+
+#[test]
+fn conflicting_priority_fails_to_build() {
+    let mut world = World::new();
+    let mut schedule = Schedule::new(Update);
+    schedule.add_build_pass(SerializeWritersPass);
+    schedule
+        .add_systems((a_writer.writes::<Marker>(), c_writer.writes::<Marker>()).chain())
+        .configure_sets(SerializedWriters::new(TypeId::of::<Marker>()));
+    assert!(schedule.initialize(&mut world).is_ok());
+
+    let mut schedule = Schedule::new(Update);
+    schedule.add_build_pass(SerializeWritersPass);
+    schedule
+        .add_systems(
+            (
+                a_writer.writes::<Marker>(),
+                c_writer.writes_with_priority::<Marker>(-1),
+            )
+                .chain(),
+        )
+        .configure_sets(SerializedWriters::new(TypeId::of::<Marker>()));
+    assert!(schedule.initialize(&mut world).is_err());
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.init_resource::<Trace>().init_resource::<Marker>();
+    app
+}
+
+fn trace(app: &App) -> Vec<&'static str> {
+    app.world().resource::<Trace>().0.clone()
+}
+
+#[derive(Resource, Default)]
+struct Trace(Vec<&'static str>);
+
+#[derive(Resource, Default)]
+struct Marker;
+
+fn a_writer(mut trace: ResMut<Trace>) {
+    trace.0.push("a");
+}
+
+fn b_modifier(mut trace: ResMut<Trace>, _marker: ResMut<Marker>) {
+    trace.0.push("b");
+}
+
+fn c_writer(mut trace: ResMut<Trace>) {
+    trace.0.push("c");
+}
+
+fn reader(mut trace: ResMut<Trace>) {
+    trace.0.push("reader");
+}
@@ -1,4 +1,7 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    any::TypeId,
+    sync::{Arc, Mutex},
+};
 
 use crate::prelude::*;
 use bevy::prelude::*;
@@ -68,6 +71,48 @@ fn writes_before_reads_test() {
     }
 }
 
+#[test]
+fn writes_before_modifies_before_reads_test() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<String>::new()));
+
+        let mut app = App::new();
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move || {
+                trace_handle.lock().unwrap().push("3".to_string());
+            })
+            .reads::<Marker>(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move || {
+                trace_handle.lock().unwrap().push("2".to_string());
+            })
+            .modifies::<Marker>(),
+        );
+
+        let trace_handle = Arc::clone(&trace);
+        app.add_systems(
+            Startup,
+            (move || {
+                trace_handle.lock().unwrap().push("1".to_string());
+            })
+            .writes::<Marker>(),
+        );
+
+        app.configure_sets(Startup, write_before_modify_before_read::<Marker>());
+
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["1", "2", "3"]);
+    }
+}
+
 #[test]
 fn with_resources() {
     for _ in 0..8 {
@@ -94,8 +139,123 @@ fn with_resources() {
     }
 }
 
+#[test]
+fn exclusive_flow_test() {
+    for _ in 0..8 {
+        let mut app = App::new();
+
+        app.add_systems(
+            Startup,
+            (
+                (|world: &mut World| world.insert_resource(Res1)).exclusive_flow::<(Res1,)>(),
+                res1_to_res2a.in_auto_sets().writes::<Res2a>(),
+            ),
+        )
+        .configure_sets(Startup, write_before_read::<Res1>());
+        app.run();
+    }
+}
+
+#[test]
+fn exclusive_systems_are_ordered_as_writers() {
+    for _ in 0..8 {
+        let trace = Arc::new(Mutex::new(Vec::<&str>::new()));
+
+        let mut app = App::new();
+
+        let (first, second, third) = (trace.clone(), trace.clone(), trace.clone());
+        app.add_systems(
+            Startup,
+            (
+                (move |_: Res<Res1>| first.lock().unwrap().push("after")).in_auto_sets(),
+                (move |_: &mut World| second.lock().unwrap().push("exclusive")).in_auto_sets(),
+                (move |_: Res<Res2a>| third.lock().unwrap().push("before")).in_auto_sets(),
+            ),
+        )
+        .insert_resource(Res1)
+        .insert_resource(Res2a)
+        .configure_sets(
+            Startup,
+            (write_before_read::<Res1>(), read_before_write::<Res2a>()),
+        );
+        app.run();
+
+        assert_eq!(*trace.lock().unwrap(), vec!["before", "exclusive", "after"]);
+    }
+}
+
+#[test]
+fn opposite_constraints_on_different_types_stay_independent() {
+    let mut app = App::new();
+    app.add_systems(Startup, (|_: Res<Res1>, _: Res<Res2a>| {}).in_auto_sets());
+    app.add_systems(
+        Update,
+        (|_: ResMut<Res1>, _: ResMut<Res2a>| {}).in_auto_sets(),
+    );
+    let constraints = || (write_before_read::<Res1>(), read_before_write::<Res2a>());
+    app.configure_sets(Startup, constraints())
+        .configure_sets(Update, constraints());
+    app.insert_resource(Res1).insert_resource(Res2a).run();
+}
+
+#[test]
+fn dynamic_sets_match_typed_ones() {
+    assert_eq!(
+        Reads::<Res1>::default().intern(),
+        ReadsDyn::new(TypeId::of::<Res1>()).intern()
+    );
+    assert_ne!(
+        Reads::<Res1>::default().intern(),
+        WritesDyn::new(TypeId::of::<Res1>()).intern()
+    );
+
+    for _ in 0..8 {
+        let mut app = App::new();
+
+        app.add_systems(
+            Startup,
+            (
+                zero_to_res1.writes_dyn(TypeId::of::<Res1>()),
+                res1_to_res2a.reads::<Res1>().writes::<Res2a>(),
+                res1_to_res2b
+                    .reads_dyn(TypeId::of::<Res1>())
+                    .writes_dyn(TypeId::of::<Res2b>()),
+                res2_to_res3.reads::<Res2a>().reads::<Res2b>(),
+            ),
+        )
+        .configure_sets(
+            Startup,
+            (
+                write_before_read::<Res1>(),
+                write_before_read_dyn(TypeId::of::<Res2a>()),
+                write_before_read::<Res2b>(),
+            ),
+        );
+        app.run();
+    }
+}
+
+#[test]
+fn component_id_sets_match_typed_ones() {
+    let mut app = App::new();
+    let component = app.world_mut().register_component::<Component1>();
+    let target = FlowTarget::component(app.world().components(), component);
+    assert_eq!(
+        Reads::<Component1>::default().intern(),
+        ReadsDyn::new(target).intern()
+    );
+
+    app.add_systems(Update, (|_query: Query<&Component1>| {}).in_auto_sets())
+        .configure_sets(Update, write_before_read_dyn(target));
+    let schedule = app.get_schedule(Update).unwrap();
+    assert_eq!(schedule.readers_of_dyn(target).len(), 1);
+}
+
 struct Marker;
 
+#[derive(Component)]
+struct Component1;
+
 #[derive(Resource)]
 struct Res1;
 
@@ -0,0 +1,416 @@
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy::{
+    ecs::{
+        archetype::ArchetypeComponentId,
+        component::{ComponentId, Tick},
+        error::Result,
+        query::Access,
+        schedule::{
+            graph::DiGraph, InternedScheduleLabel, InternedSystemSet, LogLevel, NodeId,
+            ScheduleBuildError, ScheduleBuildPass, ScheduleGraph, ScheduleLabel,
+        },
+        system::{ScheduleSystem, SystemParamValidationError},
+        world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld},
+    },
+    log::warn,
+    prelude::{App, IntoScheduleConfigs, IntoSystem, Plugin, Resource, System, SystemSet, World},
+};
+
+use crate::{
+    access::{build_only_pass, systems_in_set},
+    flow_graph::FlowGraph,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Plugin verifying at runtime that systems ordered by flow constraints, like
+/// [`write_before_read`][crate::ordering::write_before_read], actually run in that order.
+///
+/// Every system in a flow set of the given schedules is instrumented to record when it starts and
+/// finishes. A system starting before all the systems it is ordered after have finished in the
+/// same run of the schedule is a violation. With [`LogLevel::Error`], the default, it panics. With
+/// [`LogLevel::Warn`] it is logged. All violations are also available in the [`FlowVerification`]
+/// resource.
+///
+/// The plugin serializes the start of each schedule run and takes a lock around every
+/// instrumented system, so it does nothing in builds without debug assertions.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_sorting::prelude::*;
+/// # let mut app = App::new();
+/// app.add_plugins(FlowVerificationPlugin::new(Update).with_schedule(FixedUpdate));
+/// ```
+pub struct FlowVerificationPlugin {
+    schedules: Vec<InternedScheduleLabel>,
+    level: LogLevel,
+}
+
+#[allow(clippy::needless_pass_by_value)]
+impl FlowVerificationPlugin {
+    /// Creates the plugin verifying the order of systems in `schedule`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedules: vec![schedule.intern()],
+            level: LogLevel::Error,
+        }
+    }
+
+    /// Verifies the order of systems in `schedule` too.
+    #[must_use]
+    pub fn with_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedules.push(schedule.intern());
+        self
+    }
+
+    /// Sets how violations are reported. [`LogLevel::Error`] panics, [`LogLevel::Warn`] logs
+    /// them and [`LogLevel::Ignore`] only records them in [`FlowVerification`].
+    #[must_use]
+    pub fn with_level(mut self, level: LogLevel) -> Self {
+        self.level = level;
+        self
+    }
+}
+
+impl Plugin for FlowVerificationPlugin {
+    fn build(&self, app: &mut App) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        app.init_resource::<FlowVerification>();
+        for &schedule in &self.schedules {
+            let recorder = Arc::new(FlowRecorder::new(schedule, self.level.clone()));
+            app.world_mut()
+                .resource_mut::<FlowVerification>()
+                .recorders
+                .push(Arc::clone(&recorder));
+
+            let start = Arc::clone(&recorder);
+            app.add_systems(
+                schedule,
+                (move || start.begin_run()).in_set(VerificationStart),
+            );
+            app.edit_schedule(schedule, |schedule| {
+                schedule.add_build_pass(FlowVerificationPass {
+                    recorder: Arc::clone(&recorder),
+                });
+            });
+        }
+    }
+
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
+
+/// Resource collecting violations found by [`FlowVerificationPlugin`].
+#[derive(Resource, Debug, Default)]
+pub struct FlowVerification {
+    recorders: Vec<Arc<FlowRecorder>>,
+}
+
+impl FlowVerification {
+    /// Returns all violations found so far.
+    #[must_use]
+    pub fn violations(&self) -> Vec<FlowViolation> {
+        self.recorders
+            .iter()
+            .flat_map(|recorder| recorder.state().violations.clone())
+            .collect()
+    }
+}
+
+/// Pair of systems that ran in a different order than their flow constraints require.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FlowViolation {
+    /// Label of the schedule the systems run in.
+    pub schedule: String,
+    /// Name of the system that should have finished first.
+    pub before: String,
+    /// Name of the system that started too early.
+    pub after: String,
+}
+
+impl Display for FlowViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` started in `{}` before `{}` finished, although flow constraints order it after",
+            self.after, self.schedule, self.before
+        )
+    }
+}
+
+/// System set of the system marking the start of each schedule run.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct VerificationStart;
+
+/// Latest run of an instrumented system.
+#[derive(Debug)]
+struct SystemRun {
+    /// Number of the schedule run the system started in.
+    run: u64,
+    finished: bool,
+}
+
+#[derive(Debug, Default)]
+struct RecorderState {
+    /// Number of the current schedule run.
+    run: u64,
+    /// Pairs of systems that must run one after another.
+    pairs: Vec<(NodeId, NodeId)>,
+    names: HashMap<NodeId, String>,
+    runs: HashMap<NodeId, SystemRun>,
+    violations: Vec<FlowViolation>,
+}
+
+/// Timeline of instrumented systems in a single schedule.
+#[derive(Debug)]
+pub(crate) struct FlowRecorder {
+    schedule: String,
+    level: LogLevel,
+    state: Mutex<RecorderState>,
+}
+
+impl FlowRecorder {
+    pub(crate) fn new(schedule: InternedScheduleLabel, level: LogLevel) -> Self {
+        Self {
+            schedule: format!("{schedule:?}"),
+            level,
+            state: Mutex::default(),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, RecorderState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sets the pairs of systems to verify, forgetting the previous ones.
+    pub(crate) fn expect(&self, pairs: Vec<(NodeId, NodeId)>, names: HashMap<NodeId, String>) {
+        let mut state = self.state();
+        state.pairs = pairs;
+        state.names = names;
+        state.runs.clear();
+    }
+
+    pub(crate) fn begin_run(&self) {
+        self.state().run += 1;
+    }
+
+    pub(crate) fn start(&self, system: NodeId) {
+        let mut state = self.state();
+        let run = state.run;
+        let in_run = |node: &NodeId, unfinished: bool| {
+            state
+                .runs
+                .get(node)
+                .is_some_and(|last| last.run == run && (!unfinished || !last.finished))
+        };
+        let violated = state
+            .pairs
+            .iter()
+            .filter(|&&(before, after)| {
+                (after == system && in_run(&before, true))
+                    || (before == system && in_run(&after, false))
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        for (before, after) in violated {
+            let name = |node| state.names.get(&node).cloned().unwrap_or_default();
+            let violation = FlowViolation {
+                schedule: self.schedule.clone(),
+                before: name(before),
+                after: name(after),
+            };
+            match self.level {
+                LogLevel::Ignore => {}
+                LogLevel::Warn => warn!("{violation}"),
+                LogLevel::Error => panic!("{violation}"),
+            }
+            state.violations.push(violation);
+        }
+        state.runs.insert(
+            system,
+            SystemRun {
+                run,
+                finished: false,
+            },
+        );
+    }
+
+    pub(crate) fn end(&self, system: NodeId) {
+        if let Some(last) = self.state().runs.get_mut(&system) {
+            last.finished = true;
+        }
+    }
+}
+
+/// Schedule build pass instrumenting systems in flow sets and ordering the start marker before
+/// all of them.
+#[derive(Debug)]
+struct FlowVerificationPass {
+    recorder: Arc<FlowRecorder>,
+}
+
+impl ScheduleBuildPass for FlowVerificationPass {
+    build_only_pass!();
+
+    fn build(
+        &mut self,
+        world: &mut World,
+        graph: &mut ScheduleGraph,
+        dependency_flattened: &mut DiGraph,
+    ) -> Result<(), ScheduleBuildError> {
+        let start = graph
+            .system_sets()
+            .find(|(_, set, _)| set.as_dyn_eq().as_any().is::<VerificationStart>())
+            .map(|(id, _, _)| systems_in_set(graph, id));
+        for &start in start.iter().flatten() {
+            let systems = graph.systems().map(|(id, _, _)| id).collect::<Vec<_>>();
+            for system in systems {
+                if system != start {
+                    dependency_flattened.add_edge(start, system);
+                }
+            }
+        }
+
+        let flow = FlowGraph::extract_in(world, graph);
+        let mut pairs = Vec::new();
+        for &(before, after) in &flow.constraints {
+            let successors = flow.members(after);
+            for first in flow.members(before) {
+                pairs.extend(
+                    successors
+                        .iter()
+                        .filter(|&&second| second != first)
+                        .map(|&second| (first, second)),
+                );
+            }
+        }
+        let mut names = HashMap::new();
+        for &(first, second) in &pairs {
+            for system in [first, second] {
+                names
+                    .entry(system)
+                    .or_insert_with(|| graph.system_at(system).name().into_owned());
+            }
+        }
+
+        for &system in names.keys() {
+            let Some(node) = graph
+                .systems
+                .get_mut(system.index())
+                .and_then(|node| node.get_mut())
+            else {
+                continue;
+            };
+            if node.type_id() == TypeId::of::<Probe>() {
+                continue;
+            }
+            let inner = std::mem::replace(
+                node,
+                Box::new(IntoSystem::into_system(|| -> Result { Ok(()) })),
+            );
+            *node = Box::new(Probe {
+                system: inner,
+                node: system,
+                recorder: Arc::clone(&self.recorder),
+            });
+        }
+        self.recorder.expect(pairs, names);
+        Ok(())
+    }
+}
+
+/// A [`System`] recording when the inner system starts and finishes.
+struct Probe {
+    system: ScheduleSystem,
+    node: NodeId,
+    recorder: Arc<FlowRecorder>,
+}
+
+impl System for Probe {
+    type In = ();
+    type Out = Result;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.system.component_access()
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        self.system.archetype_component_access()
+    }
+
+    fn is_send(&self) -> bool {
+        self.system.is_send()
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.system.is_exclusive()
+    }
+
+    fn has_deferred(&self) -> bool {
+        self.system.has_deferred()
+    }
+
+    unsafe fn run_unsafe(&mut self, input: (), world: UnsafeWorldCell) -> Result {
+        self.recorder.start(self.node);
+        // SAFETY: the caller upholds the guarantees for the inner system, as its access is
+        // reported as the access of this system.
+        let out = unsafe { self.system.run_unsafe(input, world) };
+        self.recorder.end(self.node);
+        out
+    }
+
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.system.apply_deferred(world);
+    }
+
+    fn queue_deferred(&mut self, world: DeferredWorld) {
+        self.system.queue_deferred(world);
+    }
+
+    unsafe fn validate_param_unsafe(
+        &mut self,
+        world: UnsafeWorldCell,
+    ) -> Result<(), SystemParamValidationError> {
+        // SAFETY: the caller upholds the guarantees for the inner system.
+        unsafe { self.system.validate_param_unsafe(world) }
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.system.initialize(world);
+    }
+
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        self.system.update_archetype_component_access(world);
+    }
+
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        self.system.check_change_tick(change_tick);
+    }
+
+    fn default_system_sets(&self) -> Vec<InternedSystemSet> {
+        self.system.default_system_sets()
+    }
+
+    fn get_last_run(&self) -> Tick {
+        self.system.get_last_run()
+    }
+
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.system.set_last_run(last_run);
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use bevy::{
+    ecs::schedule::{LogLevel, NodeId, ScheduleLabel},
+    prelude::*,
+};
+
+use super::{FlowRecorder, FlowVerification, FlowVerificationPlugin};
+use crate::prelude::{write_before_read, InferFlow};
+
+#[test]
+fn ordered_systems_pass_verification() {
+    let mut app = App::new();
+    app.add_plugins(FlowVerificationPlugin::new(Update))
+        .init_resource::<Score>()
+        .add_systems(
+            Update,
+            (
+                score_reader.in_auto_sets(),
+                score_writer.in_auto_sets(),
+                score_display.in_auto_sets(),
+            ),
+        )
+        .configure_sets(Update, write_before_read::<Score>());
+
+    for _ in 0..8 {
+        app.update();
+    }
+
+    assert!(app
+        .world()
+        .resource::<FlowVerification>()
+        .violations()
+        .is_empty());
+    assert_eq!(app.world().resource::<Score>().0, 8);
+}
+
+#[test]
+fn reader_starting_before_writer_is_reported() {
+    let recorder = recorder();
+    recorder.begin_run();
+    recorder.start(READER);
+    recorder.end(READER);
+    recorder.start(WRITER);
+    recorder.end(WRITER);
+
+    let violations = recorder.state().violations.clone();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].before, "writer");
+    assert_eq!(violations[0].after, "reader");
+    assert_eq!(violations[0].schedule, "Update");
+}
+
+#[test]
+fn reader_starting_while_writer_runs_is_reported() {
+    let recorder = recorder();
+    recorder.begin_run();
+    recorder.start(WRITER);
+    recorder.start(READER);
+
+    assert_eq!(recorder.state().violations.len(), 1);
+}
+
+#[test]
+fn separate_runs_are_not_reported() {
+    let recorder = recorder();
+    recorder.begin_run();
+    recorder.start(READER);
+    recorder.end(READER);
+    recorder.begin_run();
+    recorder.start(WRITER);
+    recorder.end(WRITER);
+    recorder.start(READER);
+    recorder.end(READER);
+
+    assert!(recorder.state().violations.is_empty());
+}
+
+const WRITER: NodeId = NodeId::System(0);
+const READER: NodeId = NodeId::System(1);
+
+fn recorder() -> FlowRecorder {
+    let recorder = FlowRecorder::new(Update.intern(), LogLevel::Ignore);
+    recorder.expect(
+        vec![(WRITER, READER)],
+        HashMap::from([(WRITER, "writer".to_owned()), (READER, "reader".to_owned())]),
+    );
+    recorder
+}
+
+#[derive(Resource, Default)]
+struct Score(u32);
+
+fn score_writer(mut score: ResMut<Score>) {
+    score.0 += 1;
+}
+
+fn score_reader(_score: Res<Score>) {}
+
+fn score_display(_score: Res<Score>) {}